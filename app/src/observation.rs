@@ -1,35 +1,62 @@
 use crate::observation::resources::batched_position_update::BatchedPositionUpdate;
 use crate::observation::resources::observer::Observer;
+use crate::observation::resources::observer_sites::ObserverSites;
+use crate::observation::resources::selection::Selection;
+use crate::observation::resources::sky_appearance::SkyAppearance;
+use crate::observation::resources::sky_overlays::SkyOverlays;
 use crate::observation::resources::skyseeker::Skyseeker;
 use crate::observation::resources::time::SimulationTime;
 use bevy::app::App;
 use bevy::prelude::*;
 
 mod bundles;
-mod components;
-mod resources;
+pub mod components;
+mod grids;
+pub mod resources;
 mod systems;
 
 pub struct ObservationPlugin;
 
 impl Plugin for ObservationPlugin {
     fn build(&self, app: &mut App) {
+        let observer_sites = ObserverSites::load();
+        let observer = observer_sites
+            .current_site()
+            .map_or_else(Observer::default, |site| Observer::new(site.observer()));
+
         app.insert_resource(Skyseeker::setup())
-            .insert_resource(Observer::default())
+            .insert_resource(observer)
+            .insert_resource(observer_sites)
             .insert_resource(SimulationTime::default())
             .insert_resource(BatchedPositionUpdate::default())
+            .insert_resource(SkyOverlays::default())
+            .insert_resource(SkyAppearance::default())
+            .insert_resource(Selection::default())
             .add_systems(
                 Startup,
                 (
                     systems::setup::setup_view,
                     systems::setup::spawn_horizon,
                     systems::setup::spawn_objects,
+                    systems::setup::spawn_constellations,
+                    systems::setup::spawn_grids,
+                    systems::setup::spawn_body_label_slots,
                 ),
             )
             .add_systems(
                 Update,
                 (
+                    systems::clock::advance_simulation_time,
                     systems::position::update_positions,
+                    systems::position::update_sky_lines,
+                    systems::position::update_sky_labels,
+                    systems::label::place_labels,
+                    systems::label::place_body_labels,
+                    systems::picking::pick_body,
+                    systems::overlay::apply_overlay_visibility
+                        .run_if(resource_changed::<SkyOverlays>),
+                    systems::appearance::apply_color_saturation
+                        .run_if(resource_changed::<SkyAppearance>),
                     components::drag_look_camera::drag_look_system,
                 ),
             );
@@ -1,3 +1,10 @@
 pub mod camera;
+pub mod constellation_boundary;
+pub mod constellation_figure;
+pub mod constellation_label;
+pub mod deep_sky_ellipse;
+pub mod grid_label;
+pub mod grid_line;
 pub mod horizon;
+pub mod label_slot;
 pub mod sky_object;
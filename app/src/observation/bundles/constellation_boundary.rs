@@ -0,0 +1,55 @@
+use crate::observation::components::overlay::Overlay;
+use crate::observation::components::sky_lines::SkyLines;
+use crate::observation::components::sky_point::SkyPoint;
+use bevy::camera::visibility::NoFrustumCulling;
+use bevy::mesh::Mesh3d;
+use bevy::pbr::MeshMaterial3d;
+use bevy::prelude::{Assets, Bundle, Color, Mesh, ResMut, StandardMaterial, Transform};
+use skyseeker_core::constellation::BoundarySegment;
+
+/// Outline of one constellation, see `ConstellationBoundaries::segments`
+#[derive(Bundle)]
+pub struct ConstellationBoundary {
+    overlay: Overlay,
+    lines: SkyLines,
+    mesh: Mesh3d,
+    material: MeshMaterial3d<StandardMaterial>,
+    transform: Transform,
+    /// The vertices move every update, the bounds computed at spawn would cull the lines
+    no_frustum_culling: NoFrustumCulling,
+}
+
+impl ConstellationBoundary {
+    pub fn new(
+        segments: &[BoundarySegment],
+        meshes: &mut ResMut<Assets<Mesh>>,
+        materials: &mut ResMut<Assets<StandardMaterial>>,
+    ) -> Self {
+        let vertices = segments
+            .iter()
+            .flat_map(|segment| [segment.start, segment.end])
+            .map(|(right_ascension, declination)| SkyPoint::Catalog {
+                right_ascension: right_ascension.to_radians(),
+                declination: declination.to_radians(),
+            })
+            .collect();
+
+        let color = Color::srgb(0.45, 0.3, 0.15);
+        let lines = SkyLines { vertices };
+        let material = materials.add(StandardMaterial {
+            base_color: color,
+            emissive: color.to_linear(),
+            unlit: true,
+            ..Default::default()
+        });
+
+        Self {
+            overlay: Overlay::ConstellationBoundaries,
+            mesh: Mesh3d(meshes.add(lines.mesh())),
+            lines,
+            material: MeshMaterial3d(material),
+            transform: Transform::default(),
+            no_frustum_culling: NoFrustumCulling,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::observation::components::overlay::Overlay;
+use crate::observation::components::sky_lines::SkyLines;
+use crate::observation::components::sky_point::SkyPoint;
+use bevy::camera::visibility::NoFrustumCulling;
+use bevy::mesh::Mesh3d;
+use bevy::pbr::MeshMaterial3d;
+use bevy::prelude::{Assets, Bundle, Color, Mesh, ResMut, StandardMaterial, Transform};
+
+#[derive(Bundle)]
+pub struct ConstellationFigure {
+    overlay: Overlay,
+    lines: SkyLines,
+    mesh: Mesh3d,
+    material: MeshMaterial3d<StandardMaterial>,
+    transform: Transform,
+    /// The vertices move every update, the bounds computed at spawn would cull the lines
+    no_frustum_culling: NoFrustumCulling,
+}
+
+impl ConstellationFigure {
+    /// Returns None if none of the figure's stars are loaded
+    pub fn new(
+        figure: &skyseeker_core::constellation::ConstellationFigure,
+        skyseeker: &skyseeker_core::Skyseeker,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        materials: &mut ResMut<Assets<StandardMaterial>>,
+    ) -> Option<Self> {
+        let vertices: Vec<SkyPoint> = figure
+            .lines
+            .iter()
+            .filter_map(|(start, end)| {
+                let start = skyseeker.get_body(start)?;
+                let end = skyseeker.get_body(end)?;
+                Some([SkyPoint::Body(start.clone()), SkyPoint::Body(end.clone())])
+            })
+            .flatten()
+            .collect();
+        if vertices.is_empty() {
+            return None;
+        }
+
+        let color = Color::srgb(0.25, 0.4, 0.65);
+        let lines = SkyLines { vertices };
+        let material = materials.add(StandardMaterial {
+            base_color: color,
+            emissive: color.to_linear(),
+            unlit: true,
+            ..Default::default()
+        });
+
+        Some(Self {
+            overlay: Overlay::ConstellationFigures,
+            mesh: Mesh3d(meshes.add(lines.mesh())),
+            lines,
+            material: MeshMaterial3d(material),
+            transform: Transform::default(),
+            no_frustum_culling: NoFrustumCulling,
+        })
+    }
+}
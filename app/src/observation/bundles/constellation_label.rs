@@ -0,0 +1,57 @@
+use crate::observation::components::overlay::Overlay;
+use crate::observation::components::sky_label::SkyLabel;
+use crate::observation::components::sky_point::SkyPoint;
+use bevy::math::DVec3;
+use bevy::prelude::{Bundle, Color, Node, PositionType, Text, TextColor, TextFont};
+use bevy::utils::default;
+
+#[derive(Bundle)]
+pub struct ConstellationLabel {
+    overlay: Overlay,
+    label: SkyLabel,
+    text: Text,
+    font: TextFont,
+    color: TextColor,
+    node: Node,
+}
+
+impl ConstellationLabel {
+    /// Placed in the middle of the figure's stars, returns None if none of them are loaded
+    pub fn new(
+        figure: &skyseeker_core::constellation::ConstellationFigure,
+        skyseeker: &skyseeker_core::Skyseeker,
+    ) -> Option<Self> {
+        let constellation = figure.constellation()?;
+        let sum = figure
+            .lines
+            .iter()
+            .flat_map(|(start, end)| [start, end])
+            .filter_map(|id| skyseeker.get_body(id)?.catalog_coordinates())
+            .map(|(right_ascension, declination)| {
+                DVec3::new(
+                    declination.cos() * right_ascension.cos(),
+                    declination.cos() * right_ascension.sin(),
+                    declination.sin(),
+                )
+            })
+            .sum::<DVec3>();
+        let direction = sum.try_normalize()?;
+        let right_ascension = direction.y.atan2(direction.x);
+        let declination = direction.z.asin();
+
+        Some(Self {
+            overlay: Overlay::ConstellationLabels,
+            label: SkyLabel::new(SkyPoint::Catalog {
+                right_ascension,
+                declination,
+            }),
+            text: Text::new(constellation.name),
+            font: TextFont::from_font_size(13.0),
+            color: TextColor(Color::srgba(0.45, 0.6, 0.85, 0.8)),
+            node: Node {
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+        })
+    }
+}
@@ -0,0 +1,104 @@
+use crate::observation::components::body_label::BodyLabel;
+use crate::observation::components::celestial_body::CelestialBody;
+use crate::observation::components::ellipse_outline::EllipseOutline;
+use bevy::asset::RenderAssetUsages;
+use bevy::mesh::{Mesh3d, PrimitiveTopology};
+use bevy::pbr::MeshMaterial3d;
+use bevy::prelude::{Assets, Bundle, Color, Mesh, ResMut, StandardMaterial, Transform, Vec3};
+use skyseeker_core::celestial_body::deep_sky::DeepSkyObjectType;
+
+/// Radius of the sphere the sky objects are placed on
+const SKY_RADIUS: f32 = 3500.0;
+/// Angular size in arcminutes used when the catalog has none
+const DEFAULT_AXIS: f32 = 2.0;
+/// Smallest drawn semi-major axis in world units
+/// => Keeps compact objects like planetary nebulae visible next to the star spheres
+const MIN_SEMI_MAJOR_AXIS: f32 = 4.0;
+const OUTLINE_SEGMENTS: usize = 64;
+
+#[derive(Bundle)]
+pub struct DeepSkyEllipse {
+    body: CelestialBody,
+    outline: EllipseOutline,
+    label: BodyLabel,
+    mesh: Mesh3d,
+    material: MeshMaterial3d<StandardMaterial>,
+    transform: Transform,
+}
+
+impl DeepSkyEllipse {
+    /// Returns None if the body is not a deep-sky object
+    pub fn new(
+        body: CelestialBody,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        materials: &mut ResMut<Assets<StandardMaterial>>,
+    ) -> Option<Self> {
+        let skyseeker_core::celestial_body::CelestialBody::DeepSky(deep_sky) = body.get() else {
+            return None;
+        };
+
+        let major_axis = deep_sky.major_axis.map_or(DEFAULT_AXIS, |axis| axis as f32);
+        let minor_axis = deep_sky.minor_axis.map_or(major_axis, |axis| axis as f32);
+        // Half the axis, converted from arcminutes to degrees and projected onto the sky sphere
+        let mut semi_major = SKY_RADIUS * (major_axis / 120.0).to_radians().tan();
+        let mut semi_minor = SKY_RADIUS * (minor_axis / 120.0).to_radians().tan();
+        if semi_major < MIN_SEMI_MAJOR_AXIS {
+            semi_minor *= MIN_SEMI_MAJOR_AXIS / semi_major;
+            semi_major = MIN_SEMI_MAJOR_AXIS;
+        }
+
+        let color = outline_color(deep_sky.object_type);
+        let material = materials.add(StandardMaterial {
+            base_color: color,
+            emissive: color.to_linear(),
+            unlit: true,
+            ..Default::default()
+        });
+
+        Some(Self {
+            outline: EllipseOutline {
+                position_angle: deep_sky.position_angle.unwrap_or(0.0).to_radians() as f32,
+            },
+            label: BodyLabel::new(body.get(), deep_sky.magnitude() as f32),
+            body,
+            mesh: Mesh3d(meshes.add(unit_circle_outline())),
+            material: MeshMaterial3d(material),
+            transform: Transform::default().with_scale(Vec3::new(semi_major, semi_minor, 1.0)),
+        })
+    }
+}
+
+/// Closed line strip around the unit circle in the XY plane
+fn unit_circle_outline() -> Mesh {
+    let positions: Vec<[f32; 3]> = (0..=OUTLINE_SEGMENTS)
+        .map(|i| {
+            let angle = i as f32 / OUTLINE_SEGMENTS as f32 * std::f32::consts::TAU;
+            [angle.cos(), angle.sin(), 0.0]
+        })
+        .collect();
+    let normals = vec![[0.0, 0.0, 1.0]; positions.len()];
+
+    Mesh::new(PrimitiveTopology::LineStrip, RenderAssetUsages::default())
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+}
+
+fn outline_color(object_type: DeepSkyObjectType) -> Color {
+    match object_type {
+        DeepSkyObjectType::Galaxy
+        | DeepSkyObjectType::GalaxyPair
+        | DeepSkyObjectType::GalaxyTriplet
+        | DeepSkyObjectType::GalaxyGroup => Color::srgb(0.9, 0.5, 0.4),
+        DeepSkyObjectType::OpenCluster
+        | DeepSkyObjectType::GlobularCluster
+        | DeepSkyObjectType::Association => Color::srgb(0.9, 0.85, 0.4),
+        DeepSkyObjectType::PlanetaryNebula
+        | DeepSkyObjectType::HIIRegion
+        | DeepSkyObjectType::EmissionNebula
+        | DeepSkyObjectType::ReflectionNebula
+        | DeepSkyObjectType::Nebula
+        | DeepSkyObjectType::ClusterWithNebula
+        | DeepSkyObjectType::SupernovaRemnant => Color::srgb(0.4, 0.85, 0.6),
+        _ => Color::srgb(0.6, 0.6, 0.6),
+    }
+}
@@ -0,0 +1,37 @@
+use crate::observation::components::overlay::Overlay;
+use crate::observation::components::sky_label::SkyLabel;
+use crate::observation::components::sky_point::SkyPoint;
+use bevy::prelude::{Bundle, Color, Node, PositionType, Text, TextColor, TextFont};
+use bevy::utils::default;
+
+#[derive(Bundle)]
+pub struct GridLabel {
+    overlay: Overlay,
+    label: SkyLabel,
+    text: Text,
+    font: TextFont,
+    color: TextColor,
+    node: Node,
+}
+
+impl GridLabel {
+    pub fn new(
+        overlay: Overlay,
+        point: SkyPoint,
+        text: String,
+        font_size: f32,
+        color: Color,
+    ) -> Self {
+        Self {
+            overlay,
+            label: SkyLabel::new(point),
+            text: Text::new(text),
+            font: TextFont::from_font_size(font_size),
+            color: TextColor(color),
+            node: Node {
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+        }
+    }
+}
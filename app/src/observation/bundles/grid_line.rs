@@ -0,0 +1,51 @@
+use crate::observation::components::overlay::Overlay;
+use crate::observation::components::sky_lines::SkyLines;
+use crate::observation::components::sky_point::SkyPoint;
+use bevy::camera::visibility::NoFrustumCulling;
+use bevy::mesh::Mesh3d;
+use bevy::pbr::MeshMaterial3d;
+use bevy::prelude::{Assets, Bundle, Color, Mesh, ResMut, StandardMaterial, Transform};
+
+/// A line of a coordinate grid, a reference circle or a tick mark, drawn through its points in turn
+#[derive(Bundle)]
+pub struct GridLine {
+    overlay: Overlay,
+    lines: SkyLines,
+    mesh: Mesh3d,
+    material: MeshMaterial3d<StandardMaterial>,
+    transform: Transform,
+    /// The vertices move every update, the bounds computed at spawn would cull the lines
+    no_frustum_culling: NoFrustumCulling,
+}
+
+impl GridLine {
+    pub fn new(
+        overlay: Overlay,
+        points: &[SkyPoint],
+        color: Color,
+        meshes: &mut ResMut<Assets<Mesh>>,
+        materials: &mut ResMut<Assets<StandardMaterial>>,
+    ) -> Self {
+        let vertices = points
+            .windows(2)
+            .flat_map(|pair| [pair[0].clone(), pair[1].clone()])
+            .collect();
+
+        let lines = SkyLines { vertices };
+        let material = materials.add(StandardMaterial {
+            base_color: color,
+            emissive: color.to_linear(),
+            unlit: true,
+            ..Default::default()
+        });
+
+        Self {
+            overlay,
+            mesh: Mesh3d(meshes.add(lines.mesh())),
+            lines,
+            material: MeshMaterial3d(material),
+            transform: Transform::default(),
+            no_frustum_culling: NoFrustumCulling,
+        }
+    }
+}
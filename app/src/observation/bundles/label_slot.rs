@@ -0,0 +1,31 @@
+use crate::observation::components::body_label::LabelSlot;
+use crate::observation::components::overlay::Overlay;
+use bevy::prelude::{Bundle, Color, Display, Node, PositionType, Text, TextColor, TextFont};
+use bevy::utils::default;
+
+#[derive(Bundle)]
+pub struct BodyLabelSlot {
+    slot: LabelSlot,
+    overlay: Overlay,
+    text: Text,
+    font: TextFont,
+    color: TextColor,
+    node: Node,
+}
+
+impl BodyLabelSlot {
+    pub fn new(font_size: f32) -> Self {
+        Self {
+            slot: LabelSlot,
+            overlay: Overlay::BodyLabels,
+            text: Text::default(),
+            font: TextFont::from_font_size(font_size),
+            color: TextColor(Color::srgba(0.85, 0.85, 0.8, 0.85)),
+            node: Node {
+                position_type: PositionType::Absolute,
+                display: Display::None,
+                ..default()
+            },
+        }
+    }
+}
@@ -1,19 +1,30 @@
+use crate::observation::components::body_color::BodyColor;
+use crate::observation::components::body_label::BodyLabel;
 use crate::observation::components::celestial_body::CelestialBody;
 use bevy::mesh::Mesh3d;
 use bevy::pbr::MeshMaterial3d;
 use bevy::prelude::{
     Assets, Bundle, Color, Handle, LinearRgba, Mesh, ResMut, StandardMaterial, Transform, Vec3,
 };
+use skyseeker_core::celestial_body::planet::Planet;
+use skyseeker_core::position::time::Time;
 
 const REFERENCE_LUMINANCE: f32 = 500.0;
 const REFERENCE_MAGNITUDE: f32 = 0.0;
 const MIN_LUMINANCE: f32 = 0.0001;
 const MAX_LUMINANCE: f32 = 100000.0;
 const POGSON_RATIO: f32 = 2.512_f32;
+/// Effective temperature of the Sun in kelvin
+const SUN_TEMPERATURE: f64 = 5772.0;
+/// Temperature range of the Planckian locus fit in kelvin
+const MIN_TEMPERATURE: f64 = 1000.0;
+const MAX_TEMPERATURE: f64 = 15000.0;
 
 #[derive(Bundle)]
 pub struct SkyObject {
     body: CelestialBody,
+    color: BodyColor,
+    label: BodyLabel,
     mesh: Mesh3d,
     material: MeshMaterial3d<StandardMaterial>,
     transform: Transform,
@@ -22,6 +33,7 @@ pub struct SkyObject {
 impl SkyObject {
     pub fn new(
         body: CelestialBody,
+        time: &Time,
         meshes: &mut ResMut<Assets<Mesh>>,
         materials: &mut ResMut<Assets<StandardMaterial>>,
     ) -> Self {
@@ -35,12 +47,23 @@ impl SkyObject {
             2.0
         };
 
-        let luminance = magnitude_to_luminance(body.get().visual_magnitude() as f32);
+        // A body whose brightness cannot be computed is drawn as faintly as possible
+        let visual_magnitude = body
+            .get()
+            .visual_magnitude(time)
+            .map_or(f32::INFINITY, |magnitude| magnitude as f32);
+        let color = BodyColor {
+            color: body_color(body.get()),
+            luminance: magnitude_to_luminance(visual_magnitude),
+        };
+        let label = BodyLabel::new(body.get(), visual_magnitude);
         let mesh_handle = meshes.add(bevy::prelude::Sphere::new(1.0));
-        let material_handle = create_new_material(Color::WHITE, luminance, materials);
+        let material_handle = create_new_material(color.color, color.luminance, materials);
 
         Self {
             body,
+            color,
+            label,
             mesh: Mesh3d(mesh_handle),
             material: MeshMaterial3d(material_handle),
             transform: Transform::default().with_scale(Vec3::splat(scale)),
@@ -53,8 +76,12 @@ fn create_new_material(
     luminance: f32,
     materials: &mut ResMut<Assets<StandardMaterial>>,
 ) -> Handle<StandardMaterial> {
+    materials.add(material(color, luminance))
+}
+
+pub fn material(color: Color, luminance: f32) -> StandardMaterial {
     let rgba = color.to_linear();
-    materials.add(StandardMaterial {
+    StandardMaterial {
         base_color: color,
         emissive: LinearRgba::rgb(
             rgba.red * luminance,
@@ -64,10 +91,57 @@ fn create_new_material(
         metallic: 0.0,
         reflectance: 0.0,
         ..Default::default()
-    })
+    }
 }
 
 fn magnitude_to_luminance(magnitude: f32) -> f32 {
     let luminance = REFERENCE_LUMINANCE * POGSON_RATIO.powf(REFERENCE_MAGNITUDE - magnitude);
     luminance.clamp(MIN_LUMINANCE, MAX_LUMINANCE)
 }
+
+/// True color of a body: black body color for stars, a fixed tint for the solar system bodies
+fn body_color(body: &skyseeker_core::celestial_body::CelestialBody) -> Color {
+    use skyseeker_core::celestial_body::CelestialBody;
+
+    match body {
+        CelestialBody::Star(star) => star.temperature().map_or(Color::WHITE, blackbody_color),
+        CelestialBody::Sun => blackbody_color(SUN_TEMPERATURE),
+        CelestialBody::Moon => Color::srgb(0.96, 0.94, 0.9),
+        CelestialBody::Planet(planet) => match planet {
+            Planet::Mercury => Color::srgb(0.8, 0.76, 0.72),
+            Planet::Venus => Color::srgb(1.0, 0.97, 0.88),
+            Planet::Mars => Color::srgb(1.0, 0.62, 0.42),
+            Planet::Jupiter => Color::srgb(0.98, 0.9, 0.78),
+            Planet::Saturn => Color::srgb(0.98, 0.9, 0.68),
+            Planet::Uranus => Color::srgb(0.72, 0.92, 0.96),
+            Planet::Neptune => Color::srgb(0.58, 0.72, 1.0),
+        },
+        _ => Color::WHITE,
+    }
+}
+
+/// Color of a black body, brightest channel at full
+/// => Chromaticity on the Planckian locus from the fit of Krystek (1985), then converted from CIE XYZ to sRGB
+fn blackbody_color(temperature: f64) -> Color {
+    let t = temperature.clamp(MIN_TEMPERATURE, MAX_TEMPERATURE);
+    // CIE 1960 UCS chromaticity
+    let u = (0.860117757 + 1.54118254e-4 * t + 1.28641212e-7 * t * t)
+        / (1.0 + 8.42420235e-4 * t + 7.08145163e-7 * t * t);
+    let v = (0.317398726 + 4.22806245e-5 * t + 4.20481691e-8 * t * t)
+        / (1.0 - 2.89741816e-5 * t + 1.61456053e-7 * t * t);
+    // CIE 1931 chromaticity, then XYZ at unit luminance
+    let x = 3.0 * u / (2.0 * u - 8.0 * v + 4.0);
+    let y = 2.0 * v / (2.0 * u - 8.0 * v + 4.0);
+    let (big_x, big_y, big_z) = (x / y, 1.0, (1.0 - x - y) / y);
+
+    let red = 3.2406 * big_x - 1.5372 * big_y - 0.4986 * big_z;
+    let green = -0.9689 * big_x + 1.8758 * big_y + 0.0415 * big_z;
+    let blue = 0.0557 * big_x - 0.2040 * big_y + 1.0570 * big_z;
+    let [red, green, blue] = [red, green, blue].map(|channel| channel.max(0.0));
+    let max = red.max(green).max(blue);
+    Color::LinearRgba(LinearRgba::rgb(
+        (red / max) as f32,
+        (green / max) as f32,
+        (blue / max) as f32,
+    ))
+}
@@ -1,3 +1,10 @@
+pub mod body_color;
+pub mod body_label;
 pub mod celestial_body;
 pub mod drag_look_camera;
+pub mod ellipse_outline;
 pub mod horizon;
+pub mod overlay;
+pub mod sky_label;
+pub mod sky_lines;
+pub mod sky_point;
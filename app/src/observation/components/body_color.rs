@@ -0,0 +1,25 @@
+use bevy::prelude::{Color, Component, LinearRgba};
+
+/// Color a body is drawn in, and how bright
+#[derive(Debug, Component)]
+pub struct BodyColor {
+    /// True color, before the saturation setting is applied
+    pub color: Color,
+    pub luminance: f32,
+}
+
+impl BodyColor {
+    /// The color with its saturation scaled, 0 gives white, above 1 exaggerates the tint
+    /// => The brightest channel is kept at full, brightness comes from the luminance alone
+    pub fn saturated(&self, saturation: f32) -> Color {
+        let rgba = self.color.to_linear();
+        let luma = 0.2126 * rgba.red + 0.7152 * rgba.green + 0.0722 * rgba.blue;
+        let [red, green, blue] = [rgba.red, rgba.green, rgba.blue]
+            .map(|channel| (luma + saturation * (channel - luma)).max(0.0));
+        let max = red.max(green).max(blue);
+        if max <= 0.0 {
+            return Color::WHITE;
+        }
+        Color::LinearRgba(LinearRgba::rgb(red / max, green / max, blue / max))
+    }
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::Component;
+
+/// Name shown next to a body when there is room for it on the screen
+#[derive(Debug, Component)]
+pub struct BodyLabel {
+    pub text: String,
+    pub visual_magnitude: f32,
+    /// Labeled whatever its magnitude, e.g. the planets, the Sun and the Moon
+    pub always: bool,
+}
+
+impl BodyLabel {
+    pub fn new(
+        body: &skyseeker_core::celestial_body::CelestialBody,
+        visual_magnitude: f32,
+    ) -> Self {
+        Self {
+            text: body.display_name().to_string(),
+            visual_magnitude,
+            always: body.is_planet() || body.is_sun() || body.is_moon(),
+        }
+    }
+}
+
+/// One of a fixed pool of screen texts the body labels that fit are written into each frame
+#[derive(Debug, Component)]
+pub struct LabelSlot;
@@ -1,5 +1,9 @@
-use bevy::input::mouse::MouseMotion;
+use bevy::input::mouse::{MouseMotion, MouseWheel};
 use bevy::prelude::*;
+use std::f32::consts::{FRAC_PI_4, PI, TAU};
+
+/// Vertical field of view the camera starts with, Bevy's default
+pub const DEFAULT_FOV: f32 = FRAC_PI_4;
 
 #[derive(Component)]
 pub struct DragLookCamera {
@@ -7,8 +11,15 @@ pub struct DragLookCamera {
     pub yaw: f32,
     pub pitch: f32,
     pub smoothness: f32,
+    /// Vertical field of view in radians, the mouse wheel zooms by changing it
+    pub fov: f32,
+    pub min_fov: f32,
+    pub max_fov: f32,
+    /// Fraction of the field of view one step of the mouse wheel zooms by
+    pub zoom_sensitivity: f32,
     target_yaw: f32,
     target_pitch: f32,
+    target_fov: f32,
 }
 
 impl Default for DragLookCamera {
@@ -18,16 +29,40 @@ impl Default for DragLookCamera {
             yaw: 0.0,
             pitch: 0.0,
             smoothness: 0.5,
+            fov: DEFAULT_FOV,
+            min_fov: 1_f32.to_radians(),
+            max_fov: 100_f32.to_radians(),
+            zoom_sensitivity: 0.1,
             target_yaw: 0.0,
             target_pitch: 0.0,
+            target_fov: DEFAULT_FOV,
         }
     }
 }
 
+impl DragLookCamera {
+    /// Direction the camera looks in, azimuth (0°=N, 90°=E) and altitude in degrees as in `Position`
+    /// => The camera looks along -Z, which is south, and yaw turns it towards the east
+    pub fn horizontal(&self) -> (f64, f64) {
+        let azimuth = (180.0 - self.yaw.to_degrees() as f64).rem_euclid(360.0);
+        (azimuth, self.pitch.to_degrees() as f64)
+    }
+
+    /// Turns the camera smoothly towards an azimuth and altitude in degrees, the inverse of `horizontal`
+    /// => Takes the shorter way around, whichever way the camera has been turned before
+    pub fn look_at(&mut self, azimuth: f64, altitude: f64) {
+        let yaw = (180.0 - azimuth).to_radians() as f32;
+        self.target_yaw += (yaw - self.target_yaw + PI).rem_euclid(TAU) - PI;
+        self.target_pitch =
+            (altitude.to_radians() as f32).clamp(-89_f32.to_radians(), 89_f32.to_radians());
+    }
+}
+
 pub fn drag_look_system(
     mouse_button: Res<ButtonInput<MouseButton>>,
     mut mouse_motion: MessageReader<MouseMotion>,
-    mut query: Query<(&mut Transform, &mut DragLookCamera)>,
+    mut mouse_wheel: MessageReader<MouseWheel>,
+    mut query: Query<(&mut Transform, &mut DragLookCamera, &mut Projection)>,
     time: Res<Time>,
 ) {
     let mut delta = Vec2::ZERO;
@@ -40,18 +75,31 @@ pub fn drag_look_system(
         mouse_motion.clear();
     }
 
-    for (mut transform, mut camera) in query.iter_mut() {
+    let scroll: f32 = mouse_wheel.read().map(|wheel| wheel.y.signum()).sum();
+
+    for (mut transform, mut camera, mut projection) in query.iter_mut() {
         if delta.length_squared() > 0.0 {
-            camera.target_yaw -= delta.x * camera.sensitivity;
-            camera.target_pitch -= delta.y * camera.sensitivity;
+            // Slower when zoomed in, so a drag moves the sky along with the mouse
+            let sensitivity = camera.sensitivity * camera.fov / DEFAULT_FOV;
+            camera.target_yaw -= delta.x * sensitivity;
+            camera.target_pitch -= delta.y * sensitivity;
             camera.target_pitch = camera
                 .target_pitch
                 .clamp(-89_f32.to_radians(), 89_f32.to_radians());
         }
 
+        if scroll != 0.0 {
+            camera.target_fov = (camera.target_fov * (1.0 - camera.zoom_sensitivity).powf(scroll))
+                .clamp(camera.min_fov, camera.max_fov);
+        }
+
         let lerp_factor = 1.0 - camera.smoothness.powf(time.delta_secs() * 60.0);
         camera.yaw += (camera.target_yaw - camera.yaw) * lerp_factor;
         camera.pitch += (camera.target_pitch - camera.pitch) * lerp_factor;
+        camera.fov += (camera.target_fov - camera.fov) * lerp_factor;
         transform.rotation = Quat::from_euler(EulerRot::YXZ, camera.yaw, camera.pitch, 0.0);
+        if let Projection::Perspective(perspective) = projection.as_mut() {
+            perspective.fov = camera.fov;
+        }
     }
 }
@@ -0,0 +1,24 @@
+use bevy::math::{Mat3, Quat};
+use bevy::prelude::{Component, Vec3};
+
+/// Apparent shape of an extended object, drawn as an outline facing the camera
+#[derive(Debug, Component)]
+pub struct EllipseOutline {
+    /// Position angle of the major axis in radians
+    /// => Measured from celestial north through east
+    pub position_angle: f32,
+}
+
+impl EllipseOutline {
+    /// Rotation that lays the outline's X axis along the major axis
+    /// => `direction` points from the observer to the object, `pole` to the north celestial pole
+    pub fn orientation(&self, direction: Vec3, pole: Vec3) -> Quat {
+        let direction = direction.normalize();
+        let north = (pole - pole.dot(direction) * direction).normalize_or(Vec3::Y);
+        let east = north.cross(direction);
+
+        let major = north * self.position_angle.cos() + east * self.position_angle.sin();
+        let minor = direction.cross(major);
+        Quat::from_mat3(&Mat3::from_cols(major, minor, direction))
+    }
+}
@@ -0,0 +1,17 @@
+use bevy::prelude::Component;
+
+/// Marks an entity as part of an overlay that can be shown or hidden, see `SkyOverlays`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub enum Overlay {
+    ConstellationFigures,
+    ConstellationBoundaries,
+    ConstellationLabels,
+    HorizontalGrid,
+    EquatorialGrid,
+    Ecliptic,
+    CelestialEquator,
+    Meridian,
+    GalacticEquator,
+    Compass,
+    BodyLabels,
+}
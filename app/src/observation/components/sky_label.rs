@@ -0,0 +1,19 @@
+use crate::observation::components::sky_point::SkyPoint;
+use bevy::prelude::{Component, Vec3};
+
+/// Text on the screen that stays next to a point on the sky
+#[derive(Debug, Component)]
+pub struct SkyLabel {
+    pub point: SkyPoint,
+    /// Where the point is on the sky sphere, None until it has been placed
+    pub anchor: Option<Vec3>,
+}
+
+impl SkyLabel {
+    pub fn new(point: SkyPoint) -> Self {
+        Self {
+            point,
+            anchor: None,
+        }
+    }
+}
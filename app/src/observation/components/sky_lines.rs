@@ -0,0 +1,23 @@
+use crate::observation::components::sky_point::SkyPoint;
+use bevy::asset::RenderAssetUsages;
+use bevy::mesh::PrimitiveTopology;
+use bevy::prelude::{Component, Mesh};
+
+/// Line segments between points on the sky, the mesh is rebuilt as the sky turns
+#[derive(Debug, Component)]
+pub struct SkyLines {
+    /// Start and end of each segment in turn, as in a line list mesh
+    pub vertices: Vec<SkyPoint>,
+}
+
+impl SkyLines {
+    /// Line list mesh with a vertex for each point, all at the origin until they are placed
+    pub fn mesh(&self) -> Mesh {
+        let positions = vec![[0.0, 0.0, 0.0]; self.vertices.len()];
+        let normals = vec![[0.0, 0.0, 1.0]; self.vertices.len()];
+
+        Mesh::new(PrimitiveTopology::LineList, RenderAssetUsages::default())
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+    }
+}
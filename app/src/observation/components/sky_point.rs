@@ -0,0 +1,61 @@
+use skyseeker_core::position::astrometry::Astrometry;
+
+/// Something on the sky that overlays are drawn at, angles in radians
+#[derive(Debug, Clone)]
+pub enum SkyPoint {
+    /// Follows the body, e.g. a star at the end of a constellation line
+    Body(skyseeker_core::celestial_body::CelestialBody),
+    /// A fixed J2000.0 (ICRS) place
+    Catalog {
+        right_ascension: f64,
+        declination: f64,
+    },
+    /// A place on the true equator and equinox of date, e.g. on the celestial equator
+    Apparent {
+        right_ascension: f64,
+        declination: f64,
+    },
+    /// A place on the mean ecliptic and equinox of date
+    Ecliptic {
+        longitude: f64,
+        latitude: f64,
+    },
+    Galactic {
+        longitude: f64,
+        latitude: f64,
+    },
+    /// Fixed to the observer's horizon (0=N, π/2=E), it does not turn with the sky
+    Horizontal {
+        azimuth: f64,
+        altitude: f64,
+    },
+}
+
+impl SkyPoint {
+    /// Azimuth and altitude of the point in degrees, as in `Position`
+    pub fn horizontal_with(&self, astrometry: &Astrometry) -> Option<(f64, f64)> {
+        let position = match *self {
+            SkyPoint::Body(ref body) => body.position_with(astrometry).ok()?,
+            SkyPoint::Catalog {
+                right_ascension,
+                declination,
+            } => astrometry.observe_catalog(right_ascension, declination, 0.0, 0.0, 0.0, 0.0),
+            SkyPoint::Apparent {
+                right_ascension,
+                declination,
+            } => astrometry.observe_apparent(right_ascension, declination),
+            SkyPoint::Ecliptic {
+                longitude,
+                latitude,
+            } => astrometry.observe_ecliptic(longitude, latitude),
+            SkyPoint::Galactic {
+                longitude,
+                latitude,
+            } => astrometry.observe_galactic(longitude, latitude),
+            SkyPoint::Horizontal { azimuth, altitude } => {
+                return Some((azimuth.to_degrees(), altitude.to_degrees()));
+            }
+        };
+        Some((position.azimuth, position.altitude))
+    }
+}
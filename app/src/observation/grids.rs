@@ -0,0 +1,274 @@
+use crate::observation::components::overlay::Overlay;
+use crate::observation::components::sky_point::SkyPoint;
+use bevy::prelude::Color;
+
+/// Distance between the points of a drawn circle in degrees
+const STEP: f64 = 2.0;
+/// Half the length of a tick mark in degrees
+const TICK: f64 = 0.75;
+/// Distance of a tick's label from the line in degrees
+const LABEL_OFFSET: f64 = 2.0;
+const LABEL_SIZE: f32 = 10.0;
+const CARDINAL_POINTS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+
+/// Lines and labeled ticks of one overlay
+pub struct Grid {
+    pub overlay: Overlay,
+    pub color: Color,
+    /// Each one drawn through its points in turn, a tick mark is a line of two points
+    pub lines: Vec<Vec<SkyPoint>>,
+    pub labels: Vec<(SkyPoint, String)>,
+    pub label_size: f32,
+}
+
+pub fn grids() -> Vec<Grid> {
+    vec![
+        horizontal_grid(),
+        equatorial_grid(),
+        ecliptic(),
+        celestial_equator(),
+        meridian(),
+        galactic_equator(),
+        compass(),
+    ]
+}
+
+/// Circles of equal altitude every 10° and lines of equal azimuth every 15°, above the horizon
+fn horizontal_grid() -> Grid {
+    let mut lines = Vec::new();
+    let mut labels = Vec::new();
+    for altitude in steps(10.0, 80.0, 10.0) {
+        lines.push(
+            steps(0.0, 360.0, STEP)
+                .map(|azimuth| horizontal(azimuth, altitude))
+                .collect(),
+        );
+        for azimuth in steps(0.0, 270.0, 90.0) {
+            labels.push((
+                horizontal(azimuth + LABEL_OFFSET, altitude),
+                format!("{altitude}°"),
+            ));
+        }
+    }
+    for azimuth in steps(0.0, 345.0, 15.0) {
+        lines.push(
+            steps(0.0, 90.0, STEP)
+                .map(|altitude| horizontal(azimuth, altitude))
+                .collect(),
+        );
+        labels.push((horizontal(azimuth, LABEL_OFFSET), format!("{azimuth}°")));
+    }
+
+    Grid {
+        overlay: Overlay::HorizontalGrid,
+        color: Color::srgb(0.25, 0.45, 0.25),
+        lines,
+        labels,
+        label_size: LABEL_SIZE,
+    }
+}
+
+/// Hour circles every hour and parallels of declination every 10°, of date
+fn equatorial_grid() -> Grid {
+    let mut lines = Vec::new();
+    let mut labels = Vec::new();
+    for right_ascension in steps(0.0, 345.0, 15.0) {
+        lines.push(
+            steps(-90.0, 90.0, STEP)
+                .map(|declination| apparent(right_ascension, declination))
+                .collect(),
+        );
+        labels.push((
+            apparent(right_ascension, -LABEL_OFFSET),
+            format!("{}h", right_ascension / 15.0),
+        ));
+    }
+    for declination in steps(-80.0, 80.0, 10.0) {
+        lines.push(
+            steps(0.0, 360.0, STEP)
+                .map(|right_ascension| apparent(right_ascension, declination))
+                .collect(),
+        );
+        if declination != 0.0 {
+            for right_ascension in steps(0.0, 270.0, 90.0) {
+                labels.push((
+                    apparent(right_ascension + LABEL_OFFSET, declination),
+                    format!("{declination:+}°"),
+                ));
+            }
+        }
+    }
+
+    Grid {
+        overlay: Overlay::EquatorialGrid,
+        color: Color::srgb(0.2, 0.3, 0.5),
+        lines,
+        labels,
+        label_size: LABEL_SIZE,
+    }
+}
+
+/// Ecliptic of date with a tick every 15° of longitude
+fn ecliptic() -> Grid {
+    let mut lines = vec![steps(0.0, 360.0, STEP)
+        .map(|longitude| ecliptic_point(longitude, 0.0))
+        .collect()];
+    let mut labels = Vec::new();
+    for longitude in steps(0.0, 345.0, 15.0) {
+        lines.push(vec![
+            ecliptic_point(longitude, -TICK),
+            ecliptic_point(longitude, TICK),
+        ]);
+        labels.push((
+            ecliptic_point(longitude, LABEL_OFFSET),
+            format!("{longitude}°"),
+        ));
+    }
+
+    Grid {
+        overlay: Overlay::Ecliptic,
+        color: Color::srgb(0.7, 0.55, 0.2),
+        lines,
+        labels,
+        label_size: LABEL_SIZE,
+    }
+}
+
+/// Celestial equator of date with a tick every hour of right ascension
+fn celestial_equator() -> Grid {
+    let mut lines = vec![steps(0.0, 360.0, STEP)
+        .map(|right_ascension| apparent(right_ascension, 0.0))
+        .collect()];
+    let mut labels = Vec::new();
+    for right_ascension in steps(0.0, 345.0, 15.0) {
+        lines.push(vec![
+            apparent(right_ascension, -TICK),
+            apparent(right_ascension, TICK),
+        ]);
+        labels.push((
+            apparent(right_ascension, LABEL_OFFSET),
+            format!("{}h", right_ascension / 15.0),
+        ));
+    }
+
+    Grid {
+        overlay: Overlay::CelestialEquator,
+        color: Color::srgb(0.35, 0.55, 0.75),
+        lines,
+        labels,
+        label_size: LABEL_SIZE,
+    }
+}
+
+/// Meridian from the north point over the zenith to the south point, with a tick every 10° of altitude
+fn meridian() -> Grid {
+    let north = steps(0.0, 90.0, STEP).map(|altitude| horizontal(0.0, altitude));
+    let south = steps(0.0, 90.0 - STEP, STEP)
+        .rev()
+        .map(|altitude| horizontal(180.0, altitude));
+    let mut lines = vec![north.chain(south).collect()];
+    let mut labels = Vec::new();
+    for azimuth in [0.0, 180.0] {
+        for altitude in steps(10.0, 80.0, 10.0) {
+            // Azimuth offsets across the meridian, widened so the tick keeps its length on the sky
+            let across = 1.0 / altitude.to_radians().cos();
+            lines.push(vec![
+                horizontal(azimuth - TICK * across, altitude),
+                horizontal(azimuth + TICK * across, altitude),
+            ]);
+            labels.push((
+                horizontal(azimuth + LABEL_OFFSET * across, altitude),
+                format!("{altitude}°"),
+            ));
+        }
+    }
+
+    Grid {
+        overlay: Overlay::Meridian,
+        color: Color::srgb(0.65, 0.25, 0.25),
+        lines,
+        labels,
+        label_size: LABEL_SIZE,
+    }
+}
+
+/// Galactic equator with a tick every 30° of galactic longitude
+fn galactic_equator() -> Grid {
+    let mut lines = vec![steps(0.0, 360.0, STEP)
+        .map(|longitude| galactic(longitude, 0.0))
+        .collect()];
+    let mut labels = Vec::new();
+    for longitude in steps(0.0, 330.0, 30.0) {
+        lines.push(vec![galactic(longitude, -TICK), galactic(longitude, TICK)]);
+        labels.push((galactic(longitude, LABEL_OFFSET), format!("{longitude}°")));
+    }
+
+    Grid {
+        overlay: Overlay::GalacticEquator,
+        color: Color::srgb(0.55, 0.35, 0.65),
+        lines,
+        labels,
+        label_size: LABEL_SIZE,
+    }
+}
+
+/// Cardinal and intercardinal points with a tick every 10° of azimuth along the horizon
+/// => Ticks every 30° are longer, as on a compass card
+fn compass() -> Grid {
+    let mut lines = Vec::new();
+    for azimuth in steps(0.0, 350.0, 10.0) {
+        let length = if azimuth % 30.0 == 0.0 {
+            3.0 * TICK
+        } else {
+            TICK
+        };
+        lines.push(vec![horizontal(azimuth, 0.0), horizontal(azimuth, length)]);
+    }
+    let labels = CARDINAL_POINTS
+        .iter()
+        .zip(steps(0.0, 315.0, 45.0))
+        .map(|(name, azimuth)| (horizontal(azimuth, 2.0 * LABEL_OFFSET), name.to_string()))
+        .collect();
+
+    Grid {
+        overlay: Overlay::Compass,
+        color: Color::srgb(0.8, 0.7, 0.5),
+        lines,
+        labels,
+        label_size: 16.0,
+    }
+}
+
+/// Evenly spaced values from `from` to `to`, both included, in degrees
+fn steps(from: f64, to: f64, step: f64) -> impl DoubleEndedIterator<Item = f64> {
+    let count = ((to - from) / step).round() as usize;
+    (0..=count).map(move |i| from + i as f64 * step)
+}
+
+fn horizontal(azimuth: f64, altitude: f64) -> SkyPoint {
+    SkyPoint::Horizontal {
+        azimuth: azimuth.to_radians(),
+        altitude: altitude.to_radians(),
+    }
+}
+
+fn apparent(right_ascension: f64, declination: f64) -> SkyPoint {
+    SkyPoint::Apparent {
+        right_ascension: right_ascension.to_radians(),
+        declination: declination.to_radians(),
+    }
+}
+
+fn ecliptic_point(longitude: f64, latitude: f64) -> SkyPoint {
+    SkyPoint::Ecliptic {
+        longitude: longitude.to_radians(),
+        latitude: latitude.to_radians(),
+    }
+}
+
+fn galactic(longitude: f64, latitude: f64) -> SkyPoint {
+    SkyPoint::Galactic {
+        longitude: longitude.to_radians(),
+        latitude: latitude.to_radians(),
+    }
+}
@@ -1,4 +1,8 @@
 pub mod batched_position_update;
 pub mod observer;
+pub mod observer_sites;
+pub mod selection;
+pub mod sky_appearance;
+pub mod sky_overlays;
 pub mod skyseeker;
 pub mod time;
@@ -3,7 +3,14 @@ use bevy::prelude::Resource;
 #[derive(Debug, Resource)]
 pub struct BatchedPositionUpdate {
     pub current_index: usize,
+    /// Bodies placed per update at normal speed, see `bodies_per_update`
     pub batch_size: usize,
+    /// Next entity with `SkyLines` to place
+    pub line_index: usize,
+    /// Next entity with a `SkyLabel` to place
+    pub label_index: usize,
+    /// Overlay entities placed per update, each one holds many points
+    pub overlay_batch_size: usize,
 }
 
 impl Default for BatchedPositionUpdate {
@@ -11,6 +18,27 @@ impl Default for BatchedPositionUpdate {
         Self {
             current_index: 0,
             batch_size: 50,
+            line_index: 0,
+            label_index: 0,
+            overlay_batch_size: 8,
         }
     }
 }
+
+impl BatchedPositionUpdate {
+    /// Bodies to place in this update while the clock runs at `rate` simulated seconds per second
+    /// => Grows with the rate, so a pass over all bodies spans as much simulated time as at normal speed
+    /// => At high rates every body is placed in every update, all at the same instant
+    pub fn bodies_per_update(&self, rate: f64) -> usize {
+        scaled(self.batch_size, rate)
+    }
+
+    /// Overlay entities to place in this update, grown with the rate as `bodies_per_update`
+    pub fn overlays_per_update(&self, rate: f64) -> usize {
+        scaled(self.overlay_batch_size, rate)
+    }
+}
+
+fn scaled(batch_size: usize, rate: f64) -> usize {
+    (batch_size as f64 * rate.abs().max(1.0)) as usize
+}
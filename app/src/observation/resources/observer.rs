@@ -4,7 +4,15 @@ use bevy::prelude::Resource;
 pub struct Observer(skyseeker_core::position::observer::Observer);
 
 impl Observer {
+    pub fn new(observer: skyseeker_core::position::observer::Observer) -> Self {
+        Self(observer)
+    }
+
     pub fn get(&self) -> &skyseeker_core::position::observer::Observer {
         &self.0
     }
+
+    pub fn set(&mut self, observer: skyseeker_core::position::observer::Observer) {
+        self.0 = observer;
+    }
 }
@@ -0,0 +1,117 @@
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Named observing sites the user saved, kept in the home directory between runs
+#[derive(Debug, Default, Serialize, Deserialize, Resource)]
+pub struct ObserverSites {
+    pub sites: Vec<Site>,
+    /// Name of the site in use, restored on start
+    pub current: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Site {
+    pub name: String,
+    /// Geodetic latitude in degrees, north positive
+    pub latitude: f64,
+    /// Longitude in degrees, east positive
+    pub longitude: f64,
+    /// Height above the WGS84 ellipsoid in meters
+    pub height: Option<f64>,
+    /// Atmospheric pressure in hPa
+    pub pressure: Option<f64>,
+    /// Temperature in degrees Celsius
+    pub temperature: Option<f64>,
+    /// Relative humidity as a fraction
+    pub humidity: Option<f64>,
+    /// Observing wavelength in micrometers
+    pub wavelength: Option<f64>,
+}
+
+impl Site {
+    pub fn from_observer(
+        name: String,
+        observer: &skyseeker_core::position::observer::Observer,
+    ) -> Self {
+        Self {
+            name,
+            latitude: observer.latitude.to_degrees(),
+            longitude: observer.longitude.to_degrees(),
+            height: observer.height,
+            pressure: observer.pressure,
+            temperature: observer.temperature,
+            humidity: observer.humidity,
+            wavelength: observer.wavelength,
+        }
+    }
+
+    pub fn observer(&self) -> skyseeker_core::position::observer::Observer {
+        skyseeker_core::position::observer::Observer {
+            longitude: self.longitude.to_radians(),
+            latitude: self.latitude.to_radians(),
+            height: self.height,
+            pressure: self.pressure,
+            temperature: self.temperature,
+            humidity: self.humidity,
+            wavelength: self.wavelength,
+        }
+    }
+}
+
+impl ObserverSites {
+    /// Reads the saved sites, none if there is no file yet or it cannot be read
+    pub fn load() -> Self {
+        let Some(path) = sites_path() else {
+            return Self::default();
+        };
+        let Ok(data) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&data)
+            .inspect_err(|e| tracing::warn!("Ignoring observer sites in {}: {}", path.display(), e))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        let Some(path) = sites_path() else {
+            return;
+        };
+        let result = (|| -> std::io::Result<()> {
+            if let Some(directory) = path.parent() {
+                std::fs::create_dir_all(directory)?;
+            }
+            std::fs::write(&path, serde_json::to_string_pretty(self)?)
+        })();
+        if let Err(e) = result {
+            tracing::warn!("Failed to save observer sites to {}: {}", path.display(), e);
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Site> {
+        self.sites.iter().find(|site| site.name == name)
+    }
+
+    pub fn current_site(&self) -> Option<&Site> {
+        self.get(self.current.as_deref()?)
+    }
+
+    /// Adds the site, replacing the one with the same name
+    pub fn insert(&mut self, site: Site) {
+        match self.sites.iter_mut().find(|other| other.name == site.name) {
+            Some(other) => *other = site,
+            None => self.sites.push(site),
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.sites.retain(|site| site.name != name);
+        if self.current.as_deref() == Some(name) {
+            self.current = None;
+        }
+    }
+}
+
+fn sites_path() -> Option<PathBuf> {
+    std::env::home_dir().map(|home| home.join(".skyseeker").join("sites.json"))
+}
@@ -0,0 +1,15 @@
+use bevy::prelude::Resource;
+
+/// Body picked by clicking on it in the sky
+#[derive(Debug, Default, Resource)]
+pub struct Selection(Option<skyseeker_core::celestial_body::CelestialBody>);
+
+impl Selection {
+    pub fn get(&self) -> Option<&skyseeker_core::celestial_body::CelestialBody> {
+        self.0.as_ref()
+    }
+
+    pub fn set(&mut self, body: skyseeker_core::celestial_body::CelestialBody) {
+        self.0 = Some(body);
+    }
+}
@@ -0,0 +1,16 @@
+use bevy::prelude::Resource;
+
+/// How the bodies on the sky are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Resource)]
+pub struct SkyAppearance {
+    /// Scales the tint of stars and planets, 0 draws them white, 1 in their true colors
+    pub color_saturation: f32,
+}
+
+impl Default for SkyAppearance {
+    fn default() -> Self {
+        Self {
+            color_saturation: 1.0,
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::observation::components::overlay::Overlay;
+use bevy::prelude::Resource;
+
+/// Which overlays are drawn over the sky
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Resource)]
+pub struct SkyOverlays {
+    pub constellation_figures: bool,
+    pub constellation_boundaries: bool,
+    pub constellation_labels: bool,
+    /// Altitude and azimuth
+    pub horizontal_grid: bool,
+    /// Right ascension and declination of date
+    pub equatorial_grid: bool,
+    pub ecliptic: bool,
+    pub celestial_equator: bool,
+    pub meridian: bool,
+    pub galactic_equator: bool,
+    /// Cardinal points and azimuth ticks along the horizon
+    pub compass: bool,
+    /// Names of the bodies, as many as fit
+    pub body_labels: bool,
+}
+
+impl Default for SkyOverlays {
+    fn default() -> Self {
+        Self {
+            constellation_figures: true,
+            constellation_boundaries: false,
+            constellation_labels: true,
+            horizontal_grid: false,
+            equatorial_grid: false,
+            ecliptic: false,
+            celestial_equator: false,
+            meridian: false,
+            galactic_equator: false,
+            compass: true,
+            body_labels: true,
+        }
+    }
+}
+
+impl SkyOverlays {
+    pub fn is_shown(&self, overlay: Overlay) -> bool {
+        match overlay {
+            Overlay::ConstellationFigures => self.constellation_figures,
+            Overlay::ConstellationBoundaries => self.constellation_boundaries,
+            Overlay::ConstellationLabels => self.constellation_labels,
+            Overlay::HorizontalGrid => self.horizontal_grid,
+            Overlay::EquatorialGrid => self.equatorial_grid,
+            Overlay::Ecliptic => self.ecliptic,
+            Overlay::CelestialEquator => self.celestial_equator,
+            Overlay::Meridian => self.meridian,
+            Overlay::GalacticEquator => self.galactic_equator,
+            Overlay::Compass => self.compass,
+            Overlay::BodyLabels => self.body_labels,
+        }
+    }
+}
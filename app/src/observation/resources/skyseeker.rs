@@ -9,6 +9,24 @@ impl Skyseeker {
         let mut skyseeker = skyseeker_core::Skyseeker::new();
         skyseeker.load_standard_bodies();
         skyseeker.load_bodies(decode(include_bytes!("../../../../data/bsc5-stars.bin")).unwrap());
+        skyseeker
+            .load_bodies(decode(include_bytes!("../../../../data/openngc-deep-sky.bin")).unwrap());
+        skyseeker
+            .load_bodies(decode(include_bytes!("../../../../data/mpc-minor-bodies.bin")).unwrap());
+        skyseeker
+            .load_bodies(decode(include_bytes!("../../../../data/tle-satellites.bin")).unwrap());
+        skyseeker.load_constellation_figures(
+            decode(include_bytes!(
+                "../../../../data/stellarium-constellation-figures.bin"
+            ))
+            .unwrap(),
+        );
+        skyseeker.load_constellation_boundaries(
+            decode(include_bytes!(
+                "../../../../data/iau-constellation-boundaries.bin"
+            ))
+            .unwrap(),
+        );
         Self(skyseeker)
     }
 
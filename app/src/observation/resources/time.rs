@@ -1,10 +1,17 @@
 use bevy::prelude::Resource;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// Length of a sidereal day in seconds, one turn of the Earth relative to the stars
+pub const SIDEREAL_DAY: f64 = 86164.0905;
 
 #[derive(Debug, Resource)]
 pub struct SimulationTime {
+    /// Follows the wall clock, `custom` is ignored while set
     pub live: bool,
     pub custom: DateTime<Utc>,
+    pub paused: bool,
+    /// Simulated seconds per real second, negative to run backwards
+    pub speed: f64,
 }
 
 impl Default for SimulationTime {
@@ -12,16 +19,69 @@ impl Default for SimulationTime {
         Self {
             live: true,
             custom: Utc::now(),
+            paused: false,
+            speed: 1.0,
         }
     }
 }
 
 impl SimulationTime {
     pub fn get_skyseeker_time(&self) -> skyseeker_core::position::time::Time {
+        skyseeker_core::position::time::Time::from_datetime(self.now())
+    }
+
+    pub fn now(&self) -> DateTime<Utc> {
         if self.live {
-            skyseeker_core::position::time::Time::now()
+            Utc::now()
         } else {
-            skyseeker_core::position::time::Time::from_datetime(self.custom)
+            self.custom
         }
     }
+
+    /// Back to the wall clock at normal speed
+    pub fn reset_to_now(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Jumps to a moment, the clock keeps running from there at the same speed
+    pub fn set(&mut self, time: DateTime<Utc>) {
+        self.live = false;
+        self.custom = time;
+    }
+
+    /// Moves the clock by a number of seconds, backwards if negative
+    pub fn step(&mut self, seconds: f64) {
+        self.set(self.now() + seconds_to_delta(seconds));
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.set(self.now());
+        self.paused = paused;
+    }
+
+    pub fn set_speed(&mut self, speed: f64) {
+        self.set(self.now());
+        self.speed = speed;
+    }
+
+    /// Simulated seconds passing per real second right now
+    /// => 0 while paused, 1 while following the wall clock
+    pub fn rate(&self) -> f64 {
+        if self.paused {
+            0.0
+        } else if self.live {
+            1.0
+        } else {
+            self.speed
+        }
+    }
+
+    /// Runs the clock for `seconds` of real time, at its speed
+    pub fn advance(&mut self, seconds: f64) {
+        self.custom += seconds_to_delta(seconds * self.speed);
+    }
+}
+
+fn seconds_to_delta(seconds: f64) -> TimeDelta {
+    TimeDelta::microseconds((seconds * 1e6) as i64)
 }
@@ -1,2 +1,7 @@
+pub mod appearance;
+pub mod clock;
+pub mod label;
+pub mod overlay;
+pub mod picking;
 pub mod position;
 pub mod setup;
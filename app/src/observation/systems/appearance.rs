@@ -0,0 +1,20 @@
+use crate::observation::bundles::sky_object;
+use crate::observation::components::body_color::BodyColor;
+use crate::observation::resources::sky_appearance::SkyAppearance;
+use bevy::pbr::MeshMaterial3d;
+use bevy::prelude::{Assets, Query, Res, ResMut, StandardMaterial};
+
+pub fn apply_color_saturation(
+    appearance: Res<SkyAppearance>,
+    query: Query<(&BodyColor, &MeshMaterial3d<StandardMaterial>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (color, material) in query.iter() {
+        if let Some(material) = materials.get_mut(&material.0) {
+            *material = sky_object::material(
+                color.saturated(appearance.color_saturation),
+                color.luminance,
+            );
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use crate::observation::resources::time::SimulationTime;
+use bevy::prelude::{Res, ResMut, Time};
+
+/// Runs the simulation clock by the frame's duration times its speed
+pub fn advance_simulation_time(time: Res<Time>, mut simulation_time: ResMut<SimulationTime>) {
+    // Only touched when it runs, so it is not marked as changed while following the wall clock or paused
+    if simulation_time.live || simulation_time.paused {
+        return;
+    }
+    simulation_time.advance(time.delta_secs_f64());
+}
@@ -0,0 +1,121 @@
+use crate::observation::components::body_label::{BodyLabel, LabelSlot};
+use crate::observation::components::drag_look_camera::{DragLookCamera, DEFAULT_FOV};
+use crate::observation::components::sky_label::SkyLabel;
+use crate::observation::resources::sky_overlays::SkyOverlays;
+use bevy::prelude::{
+    Camera, ComputedNode, Display, GlobalTransform, Node, Query, Rect, Res, Text, TextFont,
+    Transform, Val, Vec2, Vec3, With,
+};
+
+/// Moves each label's text next to its point on the screen
+/// => Labels of points below the horizon or behind the camera are not displayed
+pub fn place_labels(
+    camera_query: Query<(&Camera, &GlobalTransform), With<DragLookCamera>>,
+    mut labels: Query<(&SkyLabel, &mut Node, &ComputedNode)>,
+) {
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+
+    for (label, mut node, computed) in labels.iter_mut() {
+        let viewport_position = label
+            .anchor
+            .filter(|anchor| anchor.y >= 0.0)
+            .filter(|anchor| {
+                (anchor - camera_transform.translation()).dot(*camera_transform.forward()) > 0.0
+            })
+            .and_then(|anchor| camera.world_to_viewport(camera_transform, anchor).ok());
+        let Some(viewport_position) = viewport_position else {
+            node.display = Display::None;
+            continue;
+        };
+
+        // Centered on the point
+        let size = computed.size() * computed.inverse_scale_factor();
+        node.display = Display::Flex;
+        node.left = Val::Px(viewport_position.x - size.x / 2.0);
+        node.top = Val::Px(viewport_position.y - size.y / 2.0);
+    }
+}
+
+/// Faintest magnitude labeled at the default field of view
+const LABEL_MAGNITUDE_LIMIT: f32 = 2.0;
+/// Space between a body and its label in pixels
+const LABEL_OFFSET: f32 = 6.0;
+
+/// Writes the names of the brightest bodies in view into the label slots, skipping those that would overlap
+/// => The magnitude limit grows as the view zooms in, by 5 log10 of the zoom like the sky's surface brightness
+/// => Bodies labeled always come first, then the brighter ones, so a faint star never hides a bright one's name
+pub fn place_body_labels(
+    camera_query: Query<(&Camera, &GlobalTransform, &DragLookCamera)>,
+    bodies: Query<(&BodyLabel, &Transform)>,
+    mut slots: Query<(&mut Text, &mut Node, &TextFont), With<LabelSlot>>,
+    overlays: Res<SkyOverlays>,
+) {
+    if !overlays.body_labels {
+        return;
+    }
+    let Ok((camera, camera_transform, drag_look_camera)) = camera_query.single() else {
+        return;
+    };
+
+    let magnitude_limit =
+        LABEL_MAGNITUDE_LIMIT + 5.0 * (DEFAULT_FOV / drag_look_camera.fov).log10();
+    let mut candidates: Vec<(&BodyLabel, Vec2)> = bodies
+        .iter()
+        .filter(|(label, _)| label.always || label.visual_magnitude <= magnitude_limit)
+        // Bodies are at the origin until their first position update
+        .filter(|(_, transform)| {
+            transform.translation.y >= 0.0 && transform.translation != Vec3::ZERO
+        })
+        .filter(|(_, transform)| {
+            (transform.translation - camera_transform.translation())
+                .dot(*camera_transform.forward())
+                > 0.0
+        })
+        .filter_map(|(label, transform)| {
+            let viewport_position = camera
+                .world_to_viewport(camera_transform, transform.translation)
+                .ok()?;
+            Some((label, viewport_position))
+        })
+        .collect();
+    candidates.sort_by(|(a, _), (b, _)| {
+        b.always
+            .cmp(&a.always)
+            .then(a.visual_magnitude.total_cmp(&b.visual_magnitude))
+    });
+
+    let viewport_size = camera.logical_viewport_size().unwrap_or(Vec2::ZERO);
+    let mut placed: Vec<Rect> = Vec::new();
+    let mut candidates = candidates.into_iter();
+    for (mut text, mut node, font) in slots.iter_mut() {
+        // Text is not measured until it is laid out, so estimate the size from the font
+        let label = candidates.find_map(|(label, position)| {
+            let size = Vec2::new(
+                label.text.chars().count() as f32 * font.font_size * 0.6,
+                font.font_size * 1.2,
+            );
+            let min = Vec2::new(position.x + LABEL_OFFSET, position.y - size.y / 2.0);
+            let rect = Rect::from_corners(min, min + size);
+            let on_screen = rect.min.x >= 0.0
+                && rect.min.y >= 0.0
+                && rect.max.x <= viewport_size.x
+                && rect.max.y <= viewport_size.y;
+            let free = placed.iter().all(|other| other.intersect(rect).is_empty());
+            (on_screen && free).then_some((label, rect))
+        });
+
+        let Some((label, rect)) = label else {
+            node.display = Display::None;
+            continue;
+        };
+        placed.push(rect);
+        if text.0 != label.text {
+            text.0.clone_from(&label.text);
+        }
+        node.display = Display::Flex;
+        node.left = Val::Px(rect.min.x);
+        node.top = Val::Px(rect.min.y);
+    }
+}
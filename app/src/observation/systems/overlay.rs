@@ -0,0 +1,16 @@
+use crate::observation::components::overlay::Overlay;
+use crate::observation::resources::sky_overlays::SkyOverlays;
+use bevy::prelude::{Query, Res, Visibility};
+
+pub fn apply_overlay_visibility(
+    overlays: Res<SkyOverlays>,
+    mut query: Query<(&Overlay, &mut Visibility)>,
+) {
+    for (overlay, mut visibility) in query.iter_mut() {
+        *visibility = if overlays.is_shown(*overlay) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
@@ -0,0 +1,70 @@
+use crate::observation::components::celestial_body::CelestialBody;
+use crate::observation::components::drag_look_camera::DragLookCamera;
+use crate::observation::resources::selection::Selection;
+use bevy::prelude::{
+    ButtonInput, Camera, GlobalTransform, Local, MouseButton, Query, Res, ResMut, Transform, Vec2,
+    Vec3, Window, With,
+};
+use bevy::window::PrimaryWindow;
+use bevy_egui::input::EguiWantsInput;
+
+/// Farthest a click may be from a body on the screen to select it, in pixels
+const PICK_RADIUS: f32 = 12.0;
+/// Farthest the mouse may move between press and release for it to be a click rather than a drag
+const CLICK_TOLERANCE: f32 = 4.0;
+
+/// Selects the body closest to where the sky was clicked
+/// => Dragging to look around does not select, nor does clicking on the UI
+pub fn pick_body(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<DragLookCamera>>,
+    bodies: Query<(&CelestialBody, &Transform)>,
+    egui_wants_input: Res<EguiWantsInput>,
+    mut selection: ResMut<Selection>,
+    mut pressed_at: Local<Option<Vec2>>,
+) {
+    let Some(cursor) = window.single().ok().and_then(Window::cursor_position) else {
+        return;
+    };
+
+    if mouse_button.just_pressed(MouseButton::Left) {
+        *pressed_at = (!egui_wants_input.wants_any_pointer_input()).then_some(cursor);
+    }
+    if !mouse_button.just_released(MouseButton::Left) {
+        return;
+    }
+    let Some(pressed_at) = pressed_at.take() else {
+        return;
+    };
+    if pressed_at.distance(cursor) > CLICK_TOLERANCE {
+        return;
+    }
+    let Ok((camera, camera_transform)) = camera_query.single() else {
+        return;
+    };
+
+    let closest = bodies
+        .iter()
+        // Bodies are at the origin until their first position update
+        .filter(|(_, transform)| {
+            transform.translation.y >= 0.0 && transform.translation != Vec3::ZERO
+        })
+        .filter(|(_, transform)| {
+            (transform.translation - camera_transform.translation())
+                .dot(*camera_transform.forward())
+                > 0.0
+        })
+        .filter_map(|(body, transform)| {
+            let viewport_position = camera
+                .world_to_viewport(camera_transform, transform.translation)
+                .ok()?;
+            Some((body, viewport_position.distance(cursor)))
+        })
+        .filter(|(_, distance)| *distance <= PICK_RADIUS)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+    if let Some((body, _)) = closest {
+        selection.set(body.get().clone());
+    }
+}
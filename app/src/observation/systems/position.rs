@@ -1,21 +1,30 @@
 use crate::observation::components::celestial_body::CelestialBody;
+use crate::observation::components::ellipse_outline::EllipseOutline;
+use crate::observation::components::sky_label::SkyLabel;
+use crate::observation::components::sky_lines::SkyLines;
 use crate::observation::resources::batched_position_update::BatchedPositionUpdate;
 use crate::observation::resources::observer::Observer;
-use crate::observation::resources::skyseeker::Skyseeker;
 use crate::observation::resources::time::SimulationTime;
-use bevy::prelude::{Query, Res, ResMut, Transform, Vec3};
+use bevy::mesh::Mesh3d;
+use bevy::prelude::{Assets, Mesh, Query, Res, ResMut, Transform, Vec3, Visibility};
+use skyseeker_core::position::astrometry::Astrometry;
 
 pub fn update_positions(
-    mut query: Query<(&CelestialBody, &mut Transform)>,
+    mut query: Query<(&CelestialBody, &mut Transform, Option<&EllipseOutline>)>,
     mut batched_update: ResMut<BatchedPositionUpdate>,
     res_observer: Res<Observer>,
     simulation_time: Res<SimulationTime>,
-    skyseeker: Res<Skyseeker>,
 ) {
     let observer = res_observer.get();
     let time = simulation_time.get_skyseeker_time();
     let earth_orientation =
         skyseeker_core::position::earth_orientation::EarthOrientation::default();
+    // Shared by the whole batch, setting it up is the expensive part of placing a body
+    let Ok(astrometry) = Astrometry::new(observer, &time, &earth_orientation) else {
+        return;
+    };
+
+    let celestial_pole = celestial_pole_to_vec_3(observer.latitude as f32);
 
     let total_bodies = query.iter().count();
     if total_bodies == 0 {
@@ -27,33 +36,140 @@ pub fn update_positions(
     }
 
     let start = batched_update.current_index;
-    let end = (start + batched_update.batch_size).min(total_bodies);
+    let end = (start + batched_update.bodies_per_update(simulation_time.rate())).min(total_bodies);
 
     query
         .iter_mut()
         .skip(start)
         .take(end - start)
-        .for_each(|(body, mut transform)| {
+        .for_each(|(body, mut transform, outline)| {
             let _span = tracing::span!(tracing::Level::TRACE, "calculate_body_position").entered();
 
-            let Ok(sky_position) =
-                skyseeker
-                    .get()
-                    .position(body.get().id(), observer, &time, &earth_orientation)
-            else {
+            let Ok(sky_position) = body.get().position_with(&astrometry) else {
                 return;
             };
-
             let space_position = sky_position_to_vec_3(sky_position, 3500.0);
             transform.translation = space_position;
+
+            if let Some(outline) = outline {
+                transform.rotation = outline.orientation(space_position, celestial_pole);
+            }
         });
 
     batched_update.current_index = end;
 }
 
+/// Moves the vertices of a batch of overlay lines to where their points are now
+/// => Hidden overlays are skipped, they are placed again once shown
+pub fn update_sky_lines(
+    query: Query<(&SkyLines, &Mesh3d, &Visibility)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut batched_update: ResMut<BatchedPositionUpdate>,
+    res_observer: Res<Observer>,
+    simulation_time: Res<SimulationTime>,
+) {
+    let time = simulation_time.get_skyseeker_time();
+    let earth_orientation =
+        skyseeker_core::position::earth_orientation::EarthOrientation::default();
+    let Ok(astrometry) = Astrometry::new(res_observer.get(), &time, &earth_orientation) else {
+        return;
+    };
+
+    let shown_lines = || {
+        query
+            .iter()
+            .filter(|(_, _, visibility)| **visibility != Visibility::Hidden)
+    };
+    let total_lines = shown_lines().count();
+    if total_lines == 0 {
+        return;
+    }
+
+    if batched_update.line_index >= total_lines {
+        batched_update.line_index = 0;
+    }
+
+    let start = batched_update.line_index;
+    let end = (start + batched_update.overlays_per_update(simulation_time.rate())).min(total_lines);
+
+    for (lines, mesh, _) in shown_lines().skip(start).take(end - start) {
+        let _span = tracing::span!(tracing::Level::TRACE, "calculate_line_positions").entered();
+
+        let Some(mesh) = meshes.get_mut(&mesh.0) else {
+            continue;
+        };
+        let Some(mut positions) = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(|positions| positions.as_float3())
+            .map(|positions| positions.to_vec())
+        else {
+            continue;
+        };
+        // Points that cannot be placed keep their last place
+        for (position, point) in positions.iter_mut().zip(&lines.vertices) {
+            if let Some((azimuth, altitude)) = point.horizontal_with(&astrometry) {
+                *position = horizontal_to_vec_3(azimuth, altitude, 3500.0).to_array();
+            }
+        }
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    }
+
+    batched_update.line_index = end;
+}
+
+/// Moves the anchors of a batch of labels to where their points are now
+/// => Hidden overlays are skipped, they are placed again once shown
+pub fn update_sky_labels(
+    mut query: Query<(&mut SkyLabel, &Visibility)>,
+    mut batched_update: ResMut<BatchedPositionUpdate>,
+    res_observer: Res<Observer>,
+    simulation_time: Res<SimulationTime>,
+) {
+    let time = simulation_time.get_skyseeker_time();
+    let earth_orientation =
+        skyseeker_core::position::earth_orientation::EarthOrientation::default();
+    let Ok(astrometry) = Astrometry::new(res_observer.get(), &time, &earth_orientation) else {
+        return;
+    };
+
+    let total_labels = query
+        .iter()
+        .filter(|(_, visibility)| **visibility != Visibility::Hidden)
+        .count();
+    if total_labels == 0 {
+        return;
+    }
+
+    if batched_update.label_index >= total_labels {
+        batched_update.label_index = 0;
+    }
+
+    let start = batched_update.label_index;
+    let end =
+        (start + batched_update.overlays_per_update(simulation_time.rate())).min(total_labels);
+
+    query
+        .iter_mut()
+        .filter(|(_, visibility)| **visibility != Visibility::Hidden)
+        .skip(start)
+        .take(end - start)
+        .for_each(|(mut label, _)| {
+            if let Some((azimuth, altitude)) = label.point.horizontal_with(&astrometry) {
+                label.anchor = Some(horizontal_to_vec_3(azimuth, altitude, 3500.0));
+            }
+        });
+
+    batched_update.label_index = end;
+}
+
 fn sky_position_to_vec_3(sky_position: skyseeker_core::position::Position, radius: f32) -> Vec3 {
-    let azimuth = -sky_position.azimuth.to_radians() as f32;
-    let altitude = sky_position.altitude.to_radians() as f32;
+    horizontal_to_vec_3(sky_position.azimuth, sky_position.altitude, radius)
+}
+
+/// Point on a sphere around the observer from azimuth (0°=N, 90°=E) and altitude in degrees
+pub fn horizontal_to_vec_3(azimuth: f64, altitude: f64, radius: f32) -> Vec3 {
+    let azimuth = -azimuth.to_radians() as f32;
+    let altitude = altitude.to_radians() as f32;
 
     let y = radius * altitude.sin();
     let horizontal_distance = radius * altitude.cos();
@@ -63,3 +179,8 @@ fn sky_position_to_vec_3(sky_position: skyseeker_core::position::Position, radiu
 
     Vec3::new(x, y, z)
 }
+
+/// Direction of the north celestial pole: due north, as high above the horizon as the observer's latitude
+fn celestial_pole_to_vec_3(latitude: f32) -> Vec3 {
+    Vec3::new(0.0, latitude.sin(), latitude.cos())
+}
@@ -1,11 +1,21 @@
 use crate::observation::bundles::camera;
+use crate::observation::bundles::constellation_boundary::ConstellationBoundary;
+use crate::observation::bundles::constellation_figure::ConstellationFigure;
+use crate::observation::bundles::constellation_label::ConstellationLabel;
+use crate::observation::bundles::deep_sky_ellipse::DeepSkyEllipse;
+use crate::observation::bundles::grid_label::GridLabel;
+use crate::observation::bundles::grid_line::GridLine;
 use crate::observation::bundles::horizon::Horizon;
+use crate::observation::bundles::label_slot::BodyLabelSlot;
 use crate::observation::bundles::sky_object::SkyObject;
 use crate::observation::components::celestial_body::CelestialBody;
+use crate::observation::grids;
 use crate::observation::resources::skyseeker::Skyseeker;
+use crate::observation::resources::time::SimulationTime;
 use bevy::camera::ClearColor;
 use bevy::color::Color;
 use bevy::prelude::*;
+use std::collections::BTreeMap;
 
 pub fn setup_view(mut commands: Commands, mut clear_color: ResMut<ClearColor>) {
     commands.spawn(camera::CustomCamera::default());
@@ -26,10 +36,89 @@ pub fn spawn_objects(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     skyseeker: Res<Skyseeker>,
+    simulation_time: Res<SimulationTime>,
 ) {
+    let time = simulation_time.get_skyseeker_time();
     for body in skyseeker.get().iter_bodies() {
         let celestial_body = CelestialBody::new(body.clone());
-        let sky_object = SkyObject::new(celestial_body, &mut meshes, &mut materials);
+        if body.is_deep_sky() {
+            if let Some(ellipse) = DeepSkyEllipse::new(celestial_body, &mut meshes, &mut materials)
+            {
+                commands.spawn(ellipse);
+            }
+            continue;
+        }
+        let sky_object = SkyObject::new(celestial_body, &time, &mut meshes, &mut materials);
         commands.spawn(sky_object);
     }
 }
+
+pub fn spawn_constellations(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    skyseeker: Res<Skyseeker>,
+) {
+    let skyseeker = skyseeker.get();
+    for figure in skyseeker.constellation_figures() {
+        if let Some(figure_lines) =
+            ConstellationFigure::new(figure, skyseeker, &mut meshes, &mut materials)
+        {
+            commands.spawn(figure_lines);
+        }
+        if let Some(label) = ConstellationLabel::new(figure, skyseeker) {
+            commands.spawn(label);
+        }
+    }
+
+    let mut segments_by_constellation = BTreeMap::new();
+    for segment in skyseeker.constellation_boundaries().segments() {
+        segments_by_constellation
+            .entry(segment.constellation.abbreviation)
+            .or_insert_with(Vec::new)
+            .push(segment);
+    }
+    for segments in segments_by_constellation.values() {
+        commands.spawn(ConstellationBoundary::new(
+            segments,
+            &mut meshes,
+            &mut materials,
+        ));
+    }
+}
+
+pub fn spawn_grids(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for grid in grids::grids() {
+        for line in &grid.lines {
+            commands.spawn(GridLine::new(
+                grid.overlay,
+                line,
+                grid.color,
+                &mut meshes,
+                &mut materials,
+            ));
+        }
+        for (point, text) in grid.labels {
+            commands.spawn(GridLabel::new(
+                grid.overlay,
+                point,
+                text,
+                grid.label_size,
+                grid.color,
+            ));
+        }
+    }
+}
+
+/// Most body labels shown at once
+const BODY_LABEL_SLOTS: usize = 80;
+
+pub fn spawn_body_label_slots(mut commands: Commands) {
+    for _ in 0..BODY_LABEL_SLOTS {
+        commands.spawn(BodyLabelSlot::new(12.0));
+    }
+}
@@ -4,6 +4,7 @@ use bevy_egui::EguiPrimaryContextPass;
 
 mod components;
 mod composites;
+mod format;
 mod render;
 mod setup;
 mod windows;
@@ -14,6 +15,14 @@ impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(windows::WindowManager::default())
             .add_systems(Update, setup::setup_ui.run_if(run_once))
+            .add_systems(
+                Update,
+                (
+                    render::update_info_window,
+                    render::update_search_window,
+                    render::update_observer_window,
+                ),
+            )
             .add_systems(EguiPrimaryContextPass, render::render_ui);
     }
 }
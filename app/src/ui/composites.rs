@@ -1,3 +1,5 @@
+pub mod hud;
+pub mod time_controls;
 pub mod toolbar;
 
 pub trait AppComposite: Sized {
@@ -0,0 +1,32 @@
+use crate::ui::composites::AppComposite;
+use bevy_egui::egui::{Align2, Area, Id, RichText};
+
+/// Where the camera looks, in the bottom left corner
+pub struct Hud {
+    azimuth: f64,
+    altitude: f64,
+}
+
+impl Hud {
+    /// Azimuth (0°=N, 90°=E) and altitude in degrees
+    pub fn new((azimuth, altitude): (f64, f64)) -> Self {
+        Self { azimuth, altitude }
+    }
+}
+
+impl AppComposite for Hud {
+    fn show(self, ctx: &bevy_egui::egui::Context) {
+        Area::new(Id::new("hud"))
+            .anchor(Align2::LEFT_BOTTOM, [8.0, -8.0])
+            .interactable(false)
+            .show(ctx, |ui| {
+                ui.label(
+                    RichText::new(format!(
+                        "Az {:5.1}°  Alt {:+5.1}°",
+                        self.azimuth, self.altitude
+                    ))
+                    .monospace(),
+                );
+            });
+    }
+}
@@ -0,0 +1,268 @@
+use crate::observation::resources::time::{SimulationTime, SIDEREAL_DAY};
+use crate::ui::components::toggle_button::ToggleButton;
+use crate::ui::components::AppComponent;
+use crate::ui::composites::AppComposite;
+use bevy::prelude::Mut;
+use bevy_egui::egui::{Align2, Area, ComboBox, DragValue, Frame, Id, Ui};
+use chrono::{
+    DateTime, Datelike, Local, NaiveDate, NaiveDateTime, TimeDelta, TimeZone, Timelike, Utc,
+};
+use egui_phosphor::regular;
+use skyseeker_core::events::twilight::{twilight, Twilight, TwilightBoundary};
+use skyseeker_core::position::earth_orientation::EarthOrientation;
+use skyseeker_core::position::observer::Observer;
+use skyseeker_core::position::time::Time;
+
+/// Speed factors to choose from, simulated seconds per real second
+const SPEEDS: [f64; 10] = [
+    -10000.0, -1000.0, -100.0, -10.0, -1.0, 1.0, 10.0, 100.0, 1000.0, 10000.0,
+];
+/// Step sizes in seconds with their names
+const STEPS: [(&str, f64); 4] = [
+    ("Minute", 60.0),
+    ("Hour", 3600.0),
+    ("Day", 86400.0),
+    ("Sidereal day", SIDEREAL_DAY),
+];
+/// Names of the twilight boundaries to jump to, from sunset to full darkness, see `boundary`
+const TWILIGHTS: [&str; 4] = ["Sunset", "Civil", "Nautical", "Astronomical"];
+
+/// Play/pause, speed, stepping, twilight jumps and the date and time, at the bottom of the screen
+/// => Only marks the simulation time as changed when a control is used
+pub struct TimeControls<'a> {
+    time: Mut<'a, SimulationTime>,
+    observer: &'a Observer,
+}
+
+impl<'a> TimeControls<'a> {
+    pub fn new(time: Mut<'a, SimulationTime>, observer: &'a Observer) -> Self {
+        Self { time, observer }
+    }
+
+    fn playback(&mut self, ui: &mut Ui) {
+        let mut paused = self.time.paused;
+        let icon = if paused {
+            regular::PLAY
+        } else {
+            regular::PAUSE
+        };
+        ToggleButton::new(&mut paused, icon)
+            .tooltip("Play/pause")
+            .ui(ui);
+        if paused != self.time.paused {
+            self.time.set_paused(paused);
+        }
+
+        let mut speed = self.time.speed;
+        ComboBox::from_id_salt("time_speed")
+            .selected_text(speed_text(speed))
+            .width(80.0)
+            .show_ui(ui, |ui| {
+                for option in SPEEDS {
+                    ui.selectable_value(&mut speed, option, speed_text(option));
+                }
+            })
+            .response
+            .on_hover_text("Speed");
+        if speed != self.time.speed {
+            self.time.set_speed(speed);
+        }
+
+        ui.separator();
+
+        // Which step the buttons take, kept by egui between frames
+        let step_id = Id::new("time_step");
+        let mut step = ui.data_mut(|data| *data.get_persisted_mut_or(step_id, 1_usize));
+        if ui
+            .button(regular::CARET_LEFT)
+            .on_hover_text("Step back")
+            .clicked()
+        {
+            self.time.step(-STEPS[step].1);
+        }
+        ComboBox::from_id_salt("time_step_size")
+            .selected_text(STEPS[step].0)
+            .width(100.0)
+            .show_ui(ui, |ui| {
+                for (index, (name, _)) in STEPS.iter().enumerate() {
+                    ui.selectable_value(&mut step, index, *name);
+                }
+            });
+        if ui
+            .button(regular::CARET_RIGHT)
+            .on_hover_text("Step forward")
+            .clicked()
+        {
+            self.time.step(STEPS[step].1);
+        }
+        ui.data_mut(|data| data.insert_persisted(step_id, step));
+
+        ui.separator();
+
+        let mut live = self.time.live;
+        ToggleButton::new(&mut live, "Now")
+            .tooltip("Follow the clock")
+            .ui(ui);
+        if live && !self.time.live {
+            self.time.reset_to_now();
+        }
+    }
+
+    fn twilight(&mut self, ui: &mut Ui) {
+        // Which twilight the buttons jump to, kept by egui between frames
+        let twilight_id = Id::new("time_twilight");
+        let mut kind = ui.data_mut(|data| *data.get_persisted_mut_or(twilight_id, 0_usize));
+        let dusk = ui
+            .button(format!("{} Dusk", regular::MOON))
+            .on_hover_text("Jump to this night's dusk")
+            .clicked();
+        ComboBox::from_id_salt("time_twilight_kind")
+            .selected_text(TWILIGHTS[kind])
+            .width(100.0)
+            .show_ui(ui, |ui| {
+                for (index, name) in TWILIGHTS.iter().enumerate() {
+                    ui.selectable_value(&mut kind, index, *name);
+                }
+            });
+        let dawn = ui
+            .button(format!("Dawn {}", regular::SUN_HORIZON))
+            .on_hover_text("Jump to this night's dawn")
+            .clicked();
+        ui.data_mut(|data| data.insert_persisted(twilight_id, kind));
+
+        // Searched only on a click, a search places the Sun a few hundred times
+        if !dusk && !dawn {
+            return;
+        }
+        let start = Time::from_datetime(last_noon(self.time.now(), self.observer.longitude));
+        let Ok(night) = twilight(self.observer, &start, &EarthOrientation::default()) else {
+            return;
+        };
+        let boundary = boundary(&night, kind);
+        let event = if dusk { boundary.dusk } else { boundary.dawn };
+        // Nothing to jump to when the Sun does not cross the altitude, e.g. during white nights
+        if let Some(time) = event.and_then(|event| event.to_datetime()) {
+            self.time.set(time);
+        }
+    }
+
+    fn date_time(&mut self, ui: &mut Ui) {
+        // Shown in local time or UTC, kept by egui between frames
+        let local_id = Id::new("time_local");
+        let mut local = ui.data_mut(|data| *data.get_persisted_mut_or(local_id, true));
+
+        let now = self.time.now();
+        let shown = if local {
+            now.with_timezone(&Local).naive_local()
+        } else {
+            now.naive_utc()
+        };
+
+        let mut year = shown.year();
+        let mut month = shown.month();
+        let mut day = shown.day();
+        let mut hour = shown.hour();
+        let mut minute = shown.minute();
+        let mut second = shown.second();
+        let mut changed = false;
+        changed |= ui
+            .add(DragValue::new(&mut year).range(-4000..=9999))
+            .changed();
+        ui.label("-");
+        changed |= ui.add(DragValue::new(&mut month).range(1..=12)).changed();
+        ui.label("-");
+        changed |= ui.add(DragValue::new(&mut day).range(1..=31)).changed();
+        ui.add_space(8.0);
+        changed |= ui.add(DragValue::new(&mut hour).range(0..=23)).changed();
+        ui.label(":");
+        changed |= ui.add(DragValue::new(&mut minute).range(0..=59)).changed();
+        ui.label(":");
+        changed |= ui.add(DragValue::new(&mut second).range(0..=59)).changed();
+
+        if changed && let Some(time) = date_time(year, month, day, hour, minute, second, local) {
+            self.time.set(time);
+        }
+
+        let zone = if local {
+            now.with_timezone(&Local)
+                .format("Local (UTC%:z)")
+                .to_string()
+        } else {
+            "UTC".to_string()
+        };
+        if ui
+            .selectable_label(local, zone)
+            .on_hover_text("Show local time or UTC")
+            .clicked()
+        {
+            local = !local;
+        }
+        ui.data_mut(|data| data.insert_persisted(local_id, local));
+    }
+}
+
+impl AppComposite for TimeControls<'_> {
+    fn show(mut self, ctx: &bevy_egui::egui::Context) {
+        Area::new(Id::new("time_controls"))
+            .anchor(Align2::CENTER_BOTTOM, [0.0, -8.0])
+            .show(ctx, |ui| {
+                Frame::window(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| self.playback(ui));
+                    ui.horizontal(|ui| self.twilight(ui));
+                    ui.horizontal(|ui| self.date_time(ui));
+                });
+            });
+    }
+}
+
+fn speed_text(speed: f64) -> String {
+    format!("{:+}×", speed)
+}
+
+/// The moment the fields stand for, the day is clamped to the length of the month
+fn date_time(
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    local: bool,
+) -> Option<DateTime<Utc>> {
+    let date = (1..=day)
+        .rev()
+        .find_map(|day| NaiveDate::from_ymd_opt(year, month, day))?;
+    let naive: NaiveDateTime = date.and_hms_opt(hour, minute, second)?;
+    if local {
+        Some(Local.from_local_datetime(&naive).earliest()?.to_utc())
+    } else {
+        Some(naive.and_utc())
+    }
+}
+
+/// The boundary of a night named at `index` in `TWILIGHTS`
+fn boundary(night: &Twilight, index: usize) -> &TwilightBoundary {
+    match index {
+        0 => &night.sun,
+        1 => &night.civil,
+        2 => &night.nautical,
+        _ => &night.astronomical,
+    }
+}
+
+/// The last local mean noon at or before `now`, the night after it is the one the twilight buttons jump in
+/// => Longitude in radians, east positive
+fn last_noon(now: DateTime<Utc>, longitude: f64) -> DateTime<Utc> {
+    let offset = TimeDelta::seconds((longitude.to_degrees() / 15.0 * 3600.0).round() as i64);
+    let local_now = now + offset;
+    let local_noon = local_now
+        .date_naive()
+        .and_hms_opt(12, 0, 0)
+        .map_or(local_now, |noon| noon.and_utc());
+    let local_noon = if local_noon > local_now {
+        local_noon - TimeDelta::days(1)
+    } else {
+        local_noon
+    };
+    local_noon - offset
+}
@@ -1,26 +1,106 @@
+use crate::observation::resources::sky_appearance::SkyAppearance;
+use crate::observation::resources::sky_overlays::SkyOverlays;
 use crate::ui::components::toggle_button::ToggleButton;
 use crate::ui::components::AppComponent;
 use crate::ui::composites::AppComposite;
 use crate::ui::windows::{AppWindow, WindowManager};
-use bevy_egui::egui::TopBottomPanel;
+use bevy::prelude::{DetectChangesMut, Mut};
+use bevy_egui::egui::{Slider, TopBottomPanel};
 use egui_phosphor::regular;
 
 pub struct ToolBar<'a> {
     window_manager: &'a mut WindowManager,
+    overlays: Mut<'a, SkyOverlays>,
+    appearance: Mut<'a, SkyAppearance>,
 }
 
 impl<'a> ToolBar<'a> {
-    pub fn new(window_manager: &'a mut WindowManager) -> Self {
-        Self { window_manager }
+    pub fn new(
+        window_manager: &'a mut WindowManager,
+        overlays: Mut<'a, SkyOverlays>,
+        appearance: Mut<'a, SkyAppearance>,
+    ) -> Self {
+        Self {
+            window_manager,
+            overlays,
+            appearance,
+        }
     }
 }
 
 impl AppComposite for ToolBar<'_> {
-    fn show(self, ctx: &bevy_egui::egui::Context) {
+    fn show(mut self, ctx: &bevy_egui::egui::Context) {
         TopBottomPanel::top("toolbar").show(ctx, |ui| {
-            let mut debug_open = self.window_manager.debug.is_open();
-            ToggleButton::new(&mut debug_open, regular::BUG).ui(ui);
-            self.window_manager.debug.set_open(debug_open);
+            ui.horizontal(|ui| {
+                let mut debug_open = self.window_manager.debug.is_open();
+                ToggleButton::new(&mut debug_open, regular::BUG).ui(ui);
+                self.window_manager.debug.set_open(debug_open);
+
+                let mut search_open = self.window_manager.search.is_open();
+                ToggleButton::new(&mut search_open, regular::MAGNIFYING_GLASS)
+                    .tooltip("Search")
+                    .ui(ui);
+                self.window_manager.search.set_open(search_open);
+
+                let mut observer_open = self.window_manager.observer.is_open();
+                ToggleButton::new(&mut observer_open, regular::MAP_PIN)
+                    .tooltip("Observer")
+                    .ui(ui);
+                self.window_manager.observer.set_open(observer_open);
+
+                ui.separator();
+
+                // Copied out so the overlays are only marked as changed when a button is clicked
+                let mut overlays = *self.overlays;
+                ToggleButton::new(&mut overlays.constellation_figures, regular::LINE_SEGMENTS)
+                    .tooltip("Constellation figures")
+                    .ui(ui);
+                ToggleButton::new(&mut overlays.constellation_boundaries, regular::POLYGON)
+                    .tooltip("Constellation boundaries")
+                    .ui(ui);
+                ToggleButton::new(&mut overlays.constellation_labels, regular::TEXT_AA)
+                    .tooltip("Constellation names")
+                    .ui(ui);
+                ui.separator();
+                ToggleButton::new(&mut overlays.horizontal_grid, regular::GRID_FOUR)
+                    .tooltip("Altitude/azimuth grid")
+                    .ui(ui);
+                ToggleButton::new(&mut overlays.equatorial_grid, regular::GLOBE)
+                    .tooltip("Right ascension/declination grid")
+                    .ui(ui);
+                ToggleButton::new(&mut overlays.ecliptic, regular::SUN_HORIZON)
+                    .tooltip("Ecliptic")
+                    .ui(ui);
+                ToggleButton::new(&mut overlays.celestial_equator, regular::EQUALS)
+                    .tooltip("Celestial equator")
+                    .ui(ui);
+                ToggleButton::new(&mut overlays.meridian, regular::LINE_VERTICAL)
+                    .tooltip("Meridian")
+                    .ui(ui);
+                ToggleButton::new(&mut overlays.galactic_equator, regular::SPIRAL)
+                    .tooltip("Galactic equator")
+                    .ui(ui);
+                ToggleButton::new(&mut overlays.compass, regular::COMPASS)
+                    .tooltip("Compass")
+                    .ui(ui);
+                ToggleButton::new(&mut overlays.body_labels, regular::TAG)
+                    .tooltip("Names of stars and planets")
+                    .ui(ui);
+                self.overlays.set_if_neq(overlays);
+
+                ui.separator();
+
+                let mut appearance = *self.appearance;
+                ui.menu_button(regular::PALETTE, |ui| {
+                    ui.add(
+                        Slider::new(&mut appearance.color_saturation, 0.0..=3.0)
+                            .text("Color saturation"),
+                    );
+                })
+                .response
+                .on_hover_text("Appearance");
+                self.appearance.set_if_neq(appearance);
+            });
         });
     }
 }
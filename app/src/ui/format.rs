@@ -0,0 +1,150 @@
+use skyseeker_core::math::angle_format_to_radians;
+use skyseeker_core::position::time::Time;
+use skyseeker_core::position::Distance;
+
+/// Kilometers in an astronomical unit
+const KILOMETERS_PER_AU: f64 = 149_597_870.7;
+/// Light years in a parsec
+const LIGHT_YEARS_PER_PARSEC: f64 = 3.261_563_777;
+
+/// Right ascension in degrees as hours, minutes and seconds, e.g. "05h 55m 10.3s"
+pub fn hours_minutes_seconds(degrees: f64) -> String {
+    let tenths = (degrees.rem_euclid(360.0) / 15.0 * 36000.0).round() as u64 % (24 * 36000);
+    format!(
+        "{:02}h {:02}m {:04.1}s",
+        tenths / 36000,
+        tenths / 600 % 60,
+        (tenths % 600) as f64 / 10.0
+    )
+}
+
+/// Angle in degrees as signed degrees, arcminutes and arcseconds, e.g. "+07° 24′ 25″"
+pub fn degrees_minutes_seconds(degrees: f64) -> String {
+    let sign = if degrees < 0.0 { '-' } else { '+' };
+    let seconds = (degrees.abs() * 3600.0).round() as u64;
+    format!(
+        "{}{:02}° {:02}′ {:02}″",
+        sign,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+pub fn time(time: &Time) -> String {
+    match time.to_datetime() {
+        Some(datetime) => datetime.format("%Y-%m-%d %H:%M UTC").to_string(),
+        None => "-".to_string(),
+    }
+}
+
+/// Kilometers for nearby bodies, AU in the solar system, light years for stars
+pub fn distance(distance: Distance) -> String {
+    match distance {
+        Distance::AstronomicalUnits(au) if au < 0.01 => {
+            format!("{:.0} km", au * KILOMETERS_PER_AU)
+        }
+        Distance::AstronomicalUnits(au) => format!("{:.3} AU", au),
+        Distance::Parsecs(parsecs) => format!(
+            "{:.1} ly ({:.1} pc)",
+            parsecs * LIGHT_YEARS_PER_PARSEC,
+            parsecs
+        ),
+    }
+}
+
+/// Angle in degrees from decimal ("-33.868") or sexagesimal ("33° 52′ 4″ S", "-33 52 4", "33:52:04.8") text
+/// => `positive` and `negative` are the hemisphere letters, e.g. 'N' and 'S' for latitudes
+/// => Sexagesimal angles go through `angle_format_to_radians`, which rejects minutes or seconds of 60 or more
+pub fn parse_angle(text: &str, positive: char, negative: char) -> Option<f64> {
+    let mut is_negative = false;
+    let mut numbers = String::new();
+    for c in text.trim().chars() {
+        match c.to_ascii_uppercase() {
+            '-' | '−' => is_negative = true,
+            c if c == negative => is_negative = true,
+            c if c == positive || c == '+' => {}
+            c if c.is_ascii_digit() || c == '.' => numbers.push(c),
+            _ => numbers.push(' '),
+        }
+    }
+    let parts = numbers
+        .split_whitespace()
+        .map(str::parse::<f64>)
+        .collect::<Result<Vec<_>, _>>()
+        .ok()?;
+
+    let sign = if is_negative { '-' } else { '+' };
+    let (degrees, minutes, seconds) = match parts[..] {
+        [degrees] => return Some(if is_negative { -degrees } else { degrees }),
+        [degrees, minutes] => (degrees, minutes, 0.0),
+        [degrees, minutes, seconds] => (degrees, minutes, seconds),
+        _ => return None,
+    };
+    if degrees.fract() != 0.0 || (minutes.fract() != 0.0 && seconds != 0.0) {
+        return None;
+    }
+    // Decimal minutes, as in "51 28.6"
+    let seconds = seconds + minutes.fract() * 60.0;
+    angle_format_to_radians(sign, degrees as u32, minutes as u32, seconds)
+        .ok()
+        .map(f64::to_degrees)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_angle;
+
+    fn assert_angle(text: &str, positive: char, negative: char, expected: f64) {
+        let angle = parse_angle(text, positive, negative)
+            .unwrap_or_else(|| panic!("{text:?} did not parse"));
+        assert!(
+            (angle - expected).abs() < 1e-9,
+            "{text:?} parsed as {angle}, expected {expected}"
+        );
+    }
+
+    #[test]
+    fn parses_sexagesimal_with_symbols_and_hemisphere() {
+        assert_angle(
+            "51° 28′ 38″ N",
+            'N',
+            'S',
+            51.0 + 28.0 / 60.0 + 38.0 / 3600.0,
+        );
+        assert_angle("0° 0′ 5″ W", 'E', 'W', -5.0 / 3600.0);
+    }
+
+    #[test]
+    fn parses_signed_sexagesimal() {
+        assert_angle("-33 52 4", 'N', 'S', -(33.0 + 52.0 / 60.0 + 4.0 / 3600.0));
+        assert_angle("33:52:04.8", 'N', 'S', 33.0 + 52.0 / 60.0 + 4.8 / 3600.0);
+    }
+
+    #[test]
+    fn parses_decimal_minutes() {
+        assert_angle("51 28.6", 'N', 'S', 51.0 + 28.6 / 60.0);
+    }
+
+    #[test]
+    fn parses_decimal_degrees_with_hemisphere() {
+        assert_angle("33.868 S", 'N', 'S', -33.868);
+        assert_angle("-0.0014", 'E', 'W', -0.0014);
+    }
+
+    #[test]
+    fn rejects_invalid_text() {
+        for text in [
+            "",
+            "north",
+            "51 60 0",
+            "51 28 60",
+            "51.5 28",
+            "51 28.6 10",
+            "1 2 3 4",
+            "1.2.3",
+        ] {
+            assert_eq!(parse_angle(text, 'N', 'S'), None, "{text:?}");
+        }
+    }
+}
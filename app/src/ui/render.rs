@@ -1,14 +1,166 @@
+use crate::observation::components::drag_look_camera::DragLookCamera;
+use crate::observation::resources::observer::Observer;
+use crate::observation::resources::observer_sites::{ObserverSites, Site};
+use crate::observation::resources::selection::Selection;
+use crate::observation::resources::sky_appearance::SkyAppearance;
+use crate::observation::resources::sky_overlays::SkyOverlays;
+use crate::observation::resources::skyseeker::Skyseeker;
+use crate::observation::resources::time::SimulationTime;
+use crate::ui::composites::hud::Hud;
+use crate::ui::composites::time_controls::TimeControls;
 use crate::ui::composites::toolbar::ToolBar;
 use crate::ui::composites::AppComposite;
+use crate::ui::windows::info::next_pass;
+use crate::ui::windows::observer::ObserverRequest;
+use crate::ui::windows::search::{BelowHorizon, SearchRequest};
 use crate::ui::windows::WindowManager;
-use bevy::prelude::ResMut;
+use bevy::prelude::{DetectChanges, Query, Res, ResMut};
 use bevy_egui::EguiContexts;
+use skyseeker_core::celestial_body::CelestialBody;
+use skyseeker_core::position::earth_orientation::EarthOrientation;
 
-pub fn render_ui(mut contexts: EguiContexts, mut window_manager: ResMut<WindowManager>) {
+pub fn render_ui(
+    mut contexts: EguiContexts,
+    mut window_manager: ResMut<WindowManager>,
+    overlays: ResMut<SkyOverlays>,
+    appearance: ResMut<SkyAppearance>,
+    camera: Query<&DragLookCamera>,
+    simulation_time: ResMut<SimulationTime>,
+    observer: Res<Observer>,
+) {
     let Ok(ctx) = contexts.ctx_mut() else {
         return;
     };
 
-    ToolBar::new(&mut window_manager).show(ctx);
+    ToolBar::new(&mut window_manager, overlays.into(), appearance.into()).show(ctx);
+    if let Ok(camera) = camera.single() {
+        Hud::new(camera.horizontal()).show(ctx);
+    }
+    TimeControls::new(simulation_time.into(), observer.get()).show(ctx);
     window_manager.render(ctx);
 }
+
+/// Opens the info window on a newly selected body and keeps its details current
+pub fn update_info_window(
+    mut window_manager: ResMut<WindowManager>,
+    selection: Res<Selection>,
+    skyseeker: Res<Skyseeker>,
+    observer: Res<Observer>,
+    simulation_time: Res<SimulationTime>,
+) {
+    if selection.is_changed()
+        && let Some(body) = selection.get()
+    {
+        window_manager.info.select(body.clone());
+    }
+    window_manager.info.update(
+        skyseeker.get(),
+        observer.get(),
+        &simulation_time.get_skyseeker_time(),
+    );
+}
+
+/// Carries out what was asked for in the search window
+/// => Flying to a body below the horizon tells the window when it rises instead
+pub fn update_search_window(
+    mut window_manager: ResMut<WindowManager>,
+    mut camera: Query<&mut DragLookCamera>,
+    mut selection: ResMut<Selection>,
+    mut simulation_time: ResMut<SimulationTime>,
+    skyseeker: Res<Skyseeker>,
+    observer: Res<Observer>,
+) {
+    let search = &mut window_manager.search;
+    search.update_results(skyseeker.get());
+    let Some(request) = search.take_request() else {
+        return;
+    };
+    let Ok(mut camera) = camera.single_mut() else {
+        return;
+    };
+
+    let (body, jumped_to_rise) = match request {
+        SearchRequest::FlyTo(body) => (body, false),
+        SearchRequest::JumpToRise(body, rise) => {
+            if let Some(rise) = rise.to_datetime() {
+                simulation_time.set(rise);
+            }
+            (body, true)
+        }
+    };
+    let time = simulation_time.get_skyseeker_time();
+    let earth_orientation = EarthOrientation::default();
+    let Ok(position) = body.position(observer.get(), &time, &earth_orientation) else {
+        return;
+    };
+
+    camera.look_at(position.azimuth, position.altitude);
+    // Right at its rise a body can still be a hair below the horizon, only then a margin is allowed
+    let below_horizon = if jumped_to_rise {
+        position.geometric_altitude < -1.0
+    } else {
+        position.altitude < 0.0
+    };
+    let below_horizon = below_horizon.then(|| BelowHorizon {
+        rise: match &body {
+            CelestialBody::Satellite(satellite) => {
+                next_pass(satellite, observer.get(), &time).map(|pass| pass.aos)
+            }
+            _ => body
+                .rise_transit_set(observer.get(), &time, &earth_orientation)
+                .ok()
+                .and_then(|events| events.rise),
+        },
+        body: body.clone(),
+    });
+    search.set_below_horizon(below_horizon);
+    selection.set(body);
+}
+
+/// Fills the observer window from the observer in use and carries out what was asked for in it
+/// => Saving or loading a site makes it the one restored on start, applying unsaved values clears that
+pub fn update_observer_window(
+    mut window_manager: ResMut<WindowManager>,
+    mut observer: ResMut<Observer>,
+    mut observer_sites: ResMut<ObserverSites>,
+) {
+    let window = &mut window_manager.observer;
+    if observer_sites.is_changed() {
+        window.set_sites(
+            observer_sites
+                .sites
+                .iter()
+                .map(|site| site.name.clone())
+                .collect(),
+        );
+    }
+    if window.needs_site() {
+        let name = observer_sites.current.clone().unwrap_or_default();
+        window.show_site(&Site::from_observer(name, observer.get()));
+    }
+
+    let Some(request) = window.take_request() else {
+        return;
+    };
+    match request {
+        ObserverRequest::Apply(site) => {
+            observer.set(site.observer());
+            observer_sites.current = None;
+        }
+        ObserverRequest::Save(site) => {
+            observer.set(site.observer());
+            observer_sites.current = Some(site.name.clone());
+            observer_sites.insert(site);
+        }
+        ObserverRequest::Load(name) => {
+            let Some(site) = observer_sites.get(&name).cloned() else {
+                return;
+            };
+            observer.set(site.observer());
+            window.show_site(&site);
+            observer_sites.current = Some(name);
+        }
+        ObserverRequest::Delete(name) => observer_sites.remove(&name),
+    }
+    observer_sites.save();
+}
@@ -4,15 +4,24 @@ use bevy_egui::egui::{Id, WidgetText};
 use serde::{Deserialize, Serialize};
 
 mod debug;
+pub mod info;
+pub mod observer;
+pub mod search;
 
 #[derive(Default, Serialize, Deserialize, Resource)]
 pub struct WindowManager {
     pub debug: debug::DebugWindow,
+    pub info: info::InfoWindow,
+    pub observer: observer::ObserverWindow,
+    pub search: search::SearchWindow,
 }
 
 impl WindowManager {
     pub fn render(&mut self, ctx: &egui::Context) {
         self.debug.show(ctx);
+        self.info.show(ctx);
+        self.observer.show(ctx);
+        self.search.show(ctx);
     }
 }
 
@@ -0,0 +1,288 @@
+use crate::ui::format;
+use crate::ui::windows::AppWindow;
+use bevy_egui::egui::{Grid, ScrollArea, Ui, WidgetText};
+use serde::{Deserialize, Serialize};
+use skyseeker_core::celestial_body::satellite::Satellite;
+use skyseeker_core::celestial_body::CelestialBody;
+use skyseeker_core::constellation::Constellation;
+use skyseeker_core::events::rise_set::{RiseTransitSet, Visibility};
+use skyseeker_core::events::satellite_pass::{satellite_passes, SatellitePass};
+use skyseeker_core::position::earth_orientation::EarthOrientation;
+use skyseeker_core::position::observer::Observer;
+use skyseeker_core::position::time::Time;
+use skyseeker_core::position::{Distance, Position};
+use skyseeker_core::Skyseeker;
+
+/// Longest rise and set times are kept before searching again, in days
+const RISE_SET_REFRESH: f64 = 1.0 / 24.0;
+
+/// Details of the selected body, refreshed as the simulation time changes
+#[derive(Default, Serialize, Deserialize)]
+pub struct InfoWindow {
+    is_open: bool,
+    #[serde(skip)]
+    info: Option<BodyInfo>,
+}
+
+struct BodyInfo {
+    body: CelestialBody,
+    position: Option<Position>,
+    visual_magnitude: Option<f64>,
+    constellation: Option<&'static Constellation>,
+    rise_transit_set: Option<RiseTransitSet>,
+    /// Satellites have passes instead of rise and set
+    next_pass: Option<SatellitePass>,
+    /// Julian day the rise and set were searched from
+    rise_transit_set_start: f64,
+}
+
+impl InfoWindow {
+    /// Shows the body, opening the window
+    pub fn select(&mut self, body: CelestialBody) {
+        self.info = Some(BodyInfo {
+            body,
+            position: None,
+            visual_magnitude: None,
+            constellation: None,
+            rise_transit_set: None,
+            next_pass: None,
+            rise_transit_set_start: f64::NAN,
+        });
+        self.is_open = true;
+    }
+
+    /// Recomputes where the body is at `time`
+    /// => Rise and set are searched again once the time has moved past one of them, or by an hour either way
+    /// => The clock may run backwards, so an event counts as passed on whichever side of the search it is crossed
+    pub fn update(&mut self, skyseeker: &Skyseeker, observer: &Observer, time: &Time) {
+        let Some(info) = self.info.as_mut().filter(|_| self.is_open) else {
+            return;
+        };
+        let Ok(julian_day) = time.get_julian_day() else {
+            return;
+        };
+        let earth_orientation = EarthOrientation::default();
+
+        info.position = info.body.position(observer, time, &earth_orientation).ok();
+        info.visual_magnitude = info.body.visual_magnitude(time).ok();
+        info.constellation = skyseeker
+            .constellation(info.body.id(), observer, time, &earth_orientation)
+            .ok()
+            .flatten();
+
+        let events = info
+            .rise_transit_set
+            .map_or([None, None], |events| [events.rise, events.set]);
+        let pass = info
+            .next_pass
+            .map_or([None, None], |pass| [Some(pass.aos), Some(pass.los)]);
+        let start = info.rise_transit_set_start;
+        let (earliest, latest) = (start.min(julian_day), start.max(julian_day));
+        let event_passed = events
+            .into_iter()
+            .chain(pass)
+            .flatten()
+            .filter_map(|event| event.get_julian_day().ok())
+            .any(|event| event > earliest && event < latest);
+        let fresh = (julian_day - start).abs() < RISE_SET_REFRESH;
+        if event_passed || !fresh {
+            if let CelestialBody::Satellite(satellite) = &info.body {
+                info.next_pass = next_pass(satellite, observer, time);
+            } else {
+                info.rise_transit_set = info
+                    .body
+                    .rise_transit_set(observer, time, &earth_orientation)
+                    .ok();
+            }
+            info.rise_transit_set_start = julian_day;
+        }
+    }
+}
+
+impl AppWindow for InfoWindow {
+    fn id(&self) -> &'static str {
+        "info"
+    }
+
+    fn title(&self) -> impl Into<WidgetText> {
+        match &self.info {
+            Some(info) => info.body.display_name().to_string(),
+            None => "Info".to_string(),
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    fn set_open(&mut self, open: bool) {
+        self.is_open = open;
+    }
+
+    fn render_content(&mut self, ui: &mut Ui) {
+        let Some(info) = &self.info else {
+            ui.label("Click on a body in the sky to see its details");
+            return;
+        };
+
+        Grid::new("info_grid")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                row(ui, "Id", info.body.id());
+                let names = names(&info.body);
+                if !names.is_empty() {
+                    row(ui, "Names", names.join(", "));
+                }
+                row(ui, "Type", kind(&info.body));
+                if let Some(constellation) = info.constellation {
+                    row(ui, "Constellation", constellation.name);
+                }
+                if let Some(visual_magnitude) = info.visual_magnitude {
+                    row(ui, "Magnitude", format!("{:.2}", visual_magnitude));
+                }
+                if let CelestialBody::Star(star) = &info.body {
+                    if let Some(b_v_color) = star.b_v_color {
+                        row(ui, "B-V", format!("{:+.2}", b_v_color));
+                    }
+                    if star.parallax > 0.0 {
+                        row(
+                            ui,
+                            "Distance",
+                            format::distance(Distance::Parsecs(1.0 / star.parallax)),
+                        );
+                    }
+                } else if let Some(distance) = info.position.and_then(|position| position.distance)
+                {
+                    row(ui, "Distance", format::distance(distance));
+                }
+
+                if let Some(position) = info.position {
+                    row(ui, "Azimuth", format!("{:.2}°", position.azimuth));
+                    row(
+                        ui,
+                        "Altitude",
+                        format::degrees_minutes_seconds(position.altitude),
+                    );
+                    row(
+                        ui,
+                        "Right ascension",
+                        format::hours_minutes_seconds(position.right_ascension),
+                    );
+                    row(
+                        ui,
+                        "Declination",
+                        format::degrees_minutes_seconds(position.declination),
+                    );
+                }
+
+                if let Some(events) = info.rise_transit_set {
+                    match events.visibility {
+                        Visibility::Circumpolar => row(ui, "Rise/set", "Circumpolar"),
+                        Visibility::NeverRises => row(ui, "Rise/set", "Does not rise today"),
+                        Visibility::RisesAndSets => {
+                            if let Some(rise) = events.rise {
+                                row(ui, "Rises", format::time(&rise));
+                            }
+                            if let Some(set) = events.set {
+                                row(ui, "Sets", format::time(&set));
+                            }
+                        }
+                    }
+                    if let Some(transit) = events.transit {
+                        row(
+                            ui,
+                            "Transits",
+                            format!(
+                                "{} at {:.1}°",
+                                format::time(&transit.time),
+                                transit.altitude
+                            ),
+                        );
+                    }
+                }
+                if let CelestialBody::Satellite(_) = &info.body {
+                    match info.next_pass {
+                        Some(pass) => {
+                            row(ui, "Rises", format::time(&pass.aos));
+                            row(
+                                ui,
+                                "Highest",
+                                format!(
+                                    "{} at {:.1}°",
+                                    format::time(&pass.tca),
+                                    pass.max_elevation
+                                ),
+                            );
+                            row(ui, "Sets", format::time(&pass.los));
+                        }
+                        None => row(ui, "Next pass", "None within 24 hours"),
+                    }
+                }
+            });
+
+        if let CelestialBody::Star(star) = &info.body
+            && !star.notes.is_empty()
+        {
+            ui.separator();
+            ScrollArea::vertical().max_height(160.0).show(ui, |ui| {
+                for (category, remark) in &star.notes {
+                    ui.label(format!("{}: {}", category, remark));
+                }
+            });
+        }
+    }
+}
+
+/// First complete pass of a satellite within the 24 hours following `time`
+pub fn next_pass(satellite: &Satellite, observer: &Observer, time: &Time) -> Option<SatellitePass> {
+    let end = time.add_days(1.0).ok()?;
+    satellite_passes(
+        satellite,
+        observer,
+        time,
+        &end,
+        &EarthOrientation::default(),
+    )
+    .ok()?
+    .first()
+    .copied()
+}
+
+fn row(ui: &mut Ui, label: &str, value: impl Into<WidgetText>) {
+    ui.label(label);
+    ui.label(value);
+    ui.end_row();
+}
+
+/// Every name of the body other than its id
+fn names(body: &CelestialBody) -> Vec<String> {
+    match body {
+        CelestialBody::Star(star) => [&star.common_name, &star.bayer_full, &star.name]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect(),
+        CelestialBody::DeepSky(deep_sky) => deep_sky
+            .messier_id()
+            .into_iter()
+            .chain(deep_sky.common_names.iter().cloned())
+            .collect(),
+        CelestialBody::MinorBody(minor_body) => minor_body.name.iter().cloned().collect(),
+        CelestialBody::Satellite(satellite) => satellite.name.iter().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn kind(body: &CelestialBody) -> &'static str {
+    match body {
+        CelestialBody::Star(_) => "Star",
+        CelestialBody::DeepSky(deep_sky) => deep_sky.object_type.name(),
+        CelestialBody::Planet(_) => "Planet",
+        CelestialBody::MinorBody(minor_body) if minor_body.is_comet() => "Comet",
+        CelestialBody::MinorBody(_) => "Minor planet",
+        CelestialBody::Satellite(_) => "Artificial satellite",
+        CelestialBody::Moon => "Moon",
+        CelestialBody::Sun => "Star",
+    }
+}
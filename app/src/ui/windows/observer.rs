@@ -0,0 +1,235 @@
+use crate::observation::resources::observer_sites::Site;
+use crate::ui::format;
+use crate::ui::windows::AppWindow;
+use bevy_egui::egui::{Button, Color32, DragValue, Grid, Ui, WidgetText};
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+/// Edits where the sky is observed from and keeps named sites
+#[derive(Default, Serialize, Deserialize)]
+pub struct ObserverWindow {
+    is_open: bool,
+    /// None until it is filled from the observer in use
+    #[serde(skip)]
+    form: Option<SiteForm>,
+    /// Names of the saved sites
+    #[serde(skip)]
+    sites: Vec<String>,
+    #[serde(skip)]
+    request: Option<ObserverRequest>,
+}
+
+/// The window's fields, angles are kept as typed until they are applied
+struct SiteForm {
+    name: String,
+    latitude: String,
+    longitude: String,
+    height: Option<f64>,
+    pressure: Option<f64>,
+    temperature: Option<f64>,
+    humidity: Option<f64>,
+    wavelength: Option<f64>,
+}
+
+/// What the user asked for in the window, carried out by `update_observer_window`
+pub enum ObserverRequest {
+    /// Observe from the site without saving it
+    Apply(Site),
+    /// Save the site under its name and observe from it
+    Save(Site),
+    Load(String),
+    Delete(String),
+}
+
+impl ObserverWindow {
+    pub fn needs_site(&self) -> bool {
+        self.form.is_none()
+    }
+
+    /// Fills the fields from a site
+    pub fn show_site(&mut self, site: &Site) {
+        self.form = Some(SiteForm {
+            name: site.name.clone(),
+            latitude: format!("{:.5}", site.latitude),
+            longitude: format!("{:.5}", site.longitude),
+            height: site.height,
+            pressure: site.pressure,
+            temperature: site.temperature,
+            humidity: site.humidity,
+            wavelength: site.wavelength,
+        });
+    }
+
+    pub fn set_sites(&mut self, sites: Vec<String>) {
+        self.sites = sites;
+    }
+
+    pub fn take_request(&mut self) -> Option<ObserverRequest> {
+        self.request.take()
+    }
+}
+
+impl SiteForm {
+    fn latitude(&self) -> Option<f64> {
+        format::parse_angle(&self.latitude, 'N', 'S').filter(|latitude| latitude.abs() <= 90.0)
+    }
+
+    fn longitude(&self) -> Option<f64> {
+        format::parse_angle(&self.longitude, 'E', 'W').filter(|longitude| longitude.abs() <= 180.0)
+    }
+
+    /// None while an angle cannot be read
+    fn site(&self) -> Option<Site> {
+        Some(Site {
+            name: self.name.trim().to_string(),
+            latitude: self.latitude()?,
+            longitude: self.longitude()?,
+            height: self.height,
+            pressure: self.pressure,
+            temperature: self.temperature,
+            humidity: self.humidity,
+            wavelength: self.wavelength,
+        })
+    }
+}
+
+impl AppWindow for ObserverWindow {
+    fn id(&self) -> &'static str {
+        "observer"
+    }
+
+    fn title(&self) -> impl Into<WidgetText> {
+        "Observer"
+    }
+
+    fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    fn set_open(&mut self, open: bool) {
+        self.is_open = open;
+    }
+
+    fn render_content(&mut self, ui: &mut Ui) {
+        let Some(form) = &mut self.form else {
+            return;
+        };
+
+        Grid::new("observer_grid").num_columns(3).show(ui, |ui| {
+            ui.label("Latitude");
+            ui.text_edit_singleline(&mut form.latitude)
+                .on_hover_text("Degrees, decimal or as 51° 28′ 38″ N");
+            angle_hint(ui, form.latitude());
+            ui.end_row();
+
+            ui.label("Longitude");
+            ui.text_edit_singleline(&mut form.longitude)
+                .on_hover_text("Degrees, decimal or as 0° 0′ 5″ W");
+            angle_hint(ui, form.longitude());
+            ui.end_row();
+
+            optional(
+                ui,
+                "Height",
+                &mut form.height,
+                0.0,
+                -500.0..=9000.0,
+                " m",
+                1.0,
+            );
+            optional(
+                ui,
+                "Pressure",
+                &mut form.pressure,
+                1013.25,
+                0.0..=1100.0,
+                " hPa",
+                1.0,
+            );
+            optional(
+                ui,
+                "Temperature",
+                &mut form.temperature,
+                15.0,
+                -80.0..=60.0,
+                " °C",
+                0.5,
+            );
+            optional(ui, "Humidity", &mut form.humidity, 0.5, 0.0..=1.0, "", 0.01);
+            optional(
+                ui,
+                "Wavelength",
+                &mut form.wavelength,
+                0.55,
+                0.3..=1000.0,
+                " μm",
+                0.01,
+            );
+        });
+
+        let site = form.site();
+        if ui
+            .add_enabled(site.is_some(), Button::new("Apply"))
+            .clicked()
+        {
+            self.request = site.clone().map(ObserverRequest::Apply);
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label("Name");
+            ui.text_edit_singleline(&mut form.name);
+            let can_save = site.is_some() && !form.name.trim().is_empty();
+            if ui.add_enabled(can_save, Button::new("Save site")).clicked() {
+                self.request = site.map(ObserverRequest::Save);
+            }
+        });
+
+        for name in &self.sites {
+            ui.horizontal(|ui| {
+                if ui.button(name).on_hover_text("Observe from here").clicked() {
+                    self.request = Some(ObserverRequest::Load(name.clone()));
+                }
+                if ui
+                    .small_button(egui_phosphor::regular::TRASH)
+                    .on_hover_text("Delete")
+                    .clicked()
+                {
+                    self.request = Some(ObserverRequest::Delete(name.clone()));
+                }
+            });
+        }
+    }
+}
+
+/// The angle as it was read, or why it could not be
+fn angle_hint(ui: &mut Ui, degrees: Option<f64>) {
+    match degrees {
+        Some(degrees) => ui.weak(format::degrees_minutes_seconds(degrees)),
+        None => ui.colored_label(Color32::LIGHT_RED, "Invalid angle"),
+    };
+}
+
+/// A value that falls back to the model's default when unchecked, shown greyed out
+fn optional(
+    ui: &mut Ui,
+    label: &str,
+    value: &mut Option<f64>,
+    default: f64,
+    range: RangeInclusive<f64>,
+    suffix: &str,
+    speed: f64,
+) {
+    let mut set = value.is_some();
+    ui.checkbox(&mut set, label);
+    let mut shown = value.unwrap_or(default);
+    ui.add_enabled(
+        set,
+        DragValue::new(&mut shown)
+            .range(range)
+            .suffix(suffix)
+            .speed(speed),
+    );
+    *value = set.then_some(shown);
+    ui.end_row();
+}
@@ -0,0 +1,144 @@
+use crate::ui::format;
+use crate::ui::windows::AppWindow;
+use bevy_egui::egui::{Key, ScrollArea, Ui, WidgetText};
+use serde::{Deserialize, Serialize};
+use skyseeker_core::celestial_body::CelestialBody;
+use skyseeker_core::position::time::Time;
+use skyseeker_core::Skyseeker;
+
+/// Most results listed for a query
+const MAX_RESULTS: usize = 20;
+
+/// Finds bodies by name or designation as the query is typed
+#[derive(Default, Serialize, Deserialize)]
+pub struct SearchWindow {
+    is_open: bool,
+    #[serde(skip)]
+    query: String,
+    /// Query the results are for, None until the first search
+    #[serde(skip)]
+    searched: Option<String>,
+    #[serde(skip)]
+    results: Vec<SearchResult>,
+    #[serde(skip)]
+    request: Option<SearchRequest>,
+    #[serde(skip)]
+    below_horizon: Option<BelowHorizon>,
+}
+
+struct SearchResult {
+    body: CelestialBody,
+    matched_text: String,
+}
+
+/// What the user asked for in the window, carried out by `update_search_window`
+pub enum SearchRequest {
+    /// Select the body and turn the camera to it
+    FlyTo(CelestialBody),
+    /// Set the time to when the body rises, then turn to it
+    JumpToRise(CelestialBody, Time),
+}
+
+/// The body flown to last is below the horizon
+pub struct BelowHorizon {
+    pub body: CelestialBody,
+    /// None if it does not rise within the next 24 hours
+    pub rise: Option<Time>,
+}
+
+impl SearchWindow {
+    /// Searches again if the query changed since the last search
+    pub fn update_results(&mut self, skyseeker: &Skyseeker) {
+        if self.searched.as_ref() == Some(&self.query) {
+            return;
+        }
+        self.results = skyseeker
+            .search(self.query.trim(), MAX_RESULTS)
+            .into_iter()
+            .map(|search_match| SearchResult {
+                body: search_match.body.clone(),
+                matched_text: search_match.matched_text,
+            })
+            .collect();
+        self.searched = Some(self.query.clone());
+    }
+
+    pub fn take_request(&mut self) -> Option<SearchRequest> {
+        self.request.take()
+    }
+
+    pub fn set_below_horizon(&mut self, below_horizon: Option<BelowHorizon>) {
+        self.below_horizon = below_horizon;
+    }
+}
+
+impl AppWindow for SearchWindow {
+    fn id(&self) -> &'static str {
+        "search"
+    }
+
+    fn title(&self) -> impl Into<WidgetText> {
+        "Search"
+    }
+
+    fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    fn set_open(&mut self, open: bool) {
+        self.is_open = open;
+    }
+
+    fn render_content(&mut self, ui: &mut Ui) {
+        let response = ui.text_edit_singleline(&mut self.query);
+        // Enter takes the best match
+        if response.lost_focus()
+            && ui.input(|input| input.key_pressed(Key::Enter))
+            && let Some(result) = self.results.first()
+        {
+            self.request = Some(SearchRequest::FlyTo(result.body.clone()));
+        }
+
+        if let Some(below_horizon) = &self.below_horizon {
+            ui.separator();
+            let name = below_horizon.body.display_name();
+            match below_horizon.rise {
+                Some(rise) => {
+                    ui.label(format!(
+                        "{} is below the horizon, it rises at {}",
+                        name,
+                        format::time(&rise)
+                    ));
+                    if ui.button("Jump to rise").clicked() {
+                        self.request =
+                            Some(SearchRequest::JumpToRise(below_horizon.body.clone(), rise));
+                    }
+                }
+                None => {
+                    ui.label(format!(
+                        "{} is below the horizon and does not rise within the next 24 hours",
+                        name
+                    ));
+                }
+            }
+        }
+
+        if self.results.is_empty() {
+            return;
+        }
+        ui.separator();
+        ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+            for result in &self.results {
+                let name = result.body.display_name();
+                let text = if result.matched_text == name {
+                    name.to_string()
+                } else {
+                    format!("{} ({})", name, result.matched_text)
+                };
+                if ui.selectable_label(false, text).clicked() {
+                    self.request = Some(SearchRequest::FlyTo(result.body.clone()));
+                }
+            }
+        });
+    }
+}
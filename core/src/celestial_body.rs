@@ -1,23 +1,39 @@
+use crate::celestial_body::deep_sky::DeepSkyObject;
+use crate::celestial_body::minor_body::MinorBody;
 use crate::celestial_body::planet::Planet;
+use crate::celestial_body::satellite::Satellite;
 use crate::celestial_body::star::Star;
 use crate::error::CoreResult;
+use crate::events::rise_set::{rise_transit_set, RiseTransitSet};
+use crate::position::astrometry::Astrometry;
 use crate::position::earth_orientation::EarthOrientation;
 use crate::position::observer::Observer;
 use crate::position::time::Time;
 use crate::position::Position;
 use astro::coords::EclPoint;
 use bincode::{Decode, Encode};
+use sofars::pnp::pfw06;
+use sofars::vm::{ir, pm, pmp, rx, rz, s2c, sxp, trxp};
 use std::sync::Arc;
 
-mod moon;
-mod planet;
+pub mod deep_sky;
+pub mod minor_body;
+pub mod moon;
+pub mod planet;
+pub mod satellite;
 pub mod star;
-mod sun;
+pub(crate) mod sun;
+
+/// Time light needs to travel 1 AU in days
+pub(crate) const LIGHT_TIME_PER_AU: f64 = 0.0057755183;
 
 #[derive(Debug, Clone, Encode, Decode)]
 pub enum CelestialBody {
     Star(Arc<Star>),
+    DeepSky(Arc<DeepSkyObject>),
     Planet(Planet),
+    MinorBody(Arc<MinorBody>),
+    Satellite(Arc<Satellite>),
     Moon,
     Sun,
 }
@@ -40,7 +56,10 @@ impl CelestialBody {
     pub fn id(&self) -> &str {
         match self {
             CelestialBody::Star(star) => &star.id,
+            CelestialBody::DeepSky(deep_sky) => &deep_sky.id,
             CelestialBody::Planet(planet) => planet.id(),
+            CelestialBody::MinorBody(minor_body) => &minor_body.id,
+            CelestialBody::Satellite(satellite) => &satellite.id,
             CelestialBody::Moon => "Moon",
             CelestialBody::Sun => "Sun",
         }
@@ -52,26 +71,104 @@ impl CelestialBody {
         time: &Time,
         earth_orientation: &EarthOrientation,
     ) -> CoreResult<Position> {
+        let astrometry = Astrometry::new(observer, time, earth_orientation)?;
+        self.position_with(&astrometry)
+    }
+
+    /// Observed position using already set up astrometry parameters
+    /// => Prefer this when placing many bodies for the same observer and instant
+    pub fn position_with(&self, astrometry: &Astrometry) -> CoreResult<Position> {
+        match self {
+            CelestialBody::Star(star) => Ok(star.position_with(astrometry)),
+            CelestialBody::DeepSky(deep_sky) => Ok(deep_sky.position_with(astrometry)),
+            CelestialBody::Planet(planet) => Ok(planet.position_with(astrometry)),
+            CelestialBody::MinorBody(minor_body) => Ok(minor_body.position_with(astrometry)),
+            CelestialBody::Satellite(satellite) => satellite.position_with(astrometry),
+            CelestialBody::Moon => Ok(moon::moon_position(astrometry)),
+            CelestialBody::Sun => Ok(sun::sun_position(astrometry)),
+        }
+    }
+
+    /// Rise, upper transit and set within the 24 hours following `start`
+    /// => Not for satellites, see `events::satellite_pass::satellite_passes`
+    pub fn rise_transit_set(
+        &self,
+        observer: &Observer,
+        start: &Time,
+        earth_orientation: &EarthOrientation,
+    ) -> CoreResult<RiseTransitSet> {
+        rise_transit_set(self, observer, start, earth_orientation)
+    }
+
+    /// Apparent visual magnitude at `time`
+    /// => Fails for minor bodies outside the years covered by the Earth ephemeris
+    pub fn visual_magnitude(&self, time: &Time) -> CoreResult<f64> {
         match self {
-            CelestialBody::Star(star) => star.position(observer, time, earth_orientation),
-            CelestialBody::Planet(planet) => Ok(planet.position(observer, time)),
-            CelestialBody::Moon => Ok(moon::moon_position(observer, time)),
-            CelestialBody::Sun => Ok(sun::sun_position(observer, time)),
+            Self::Star(star) => Ok(star.visual_magnitude),
+            Self::DeepSky(deep_sky) => Ok(deep_sky.magnitude()),
+            Self::Planet(planet) => Ok(planet.appearance(time).visual_magnitude),
+            Self::MinorBody(minor_body) => Ok(minor_body.appearance(time)?.visual_magnitude),
+            Self::Satellite(_) => Ok(satellite::NOMINAL_MAGNITUDE),
+            Self::Moon => Ok(moon::moon_visual_magnitude(time)),
+            Self::Sun => Ok(-14.0),
         }
     }
 
-    pub fn visual_magnitude(&self) -> f64 {
+    /// Name to show for the body: its common name if it has one, else its designation
+    /// => For stars the common name, then the full Bayer designation, then the id
+    pub fn display_name(&self) -> &str {
         match self {
-            Self::Star(star) => star.visual_magnitude,
-            Self::Moon => -3.0,
-            Self::Sun => -14.0,
-            _ => 0.0,
+            Self::Star(star) => star
+                .common_name
+                .as_deref()
+                .or(star.bayer_full.as_deref())
+                .unwrap_or(&star.id),
+            Self::DeepSky(deep_sky) => deep_sky
+                .common_names
+                .first()
+                .map_or(&deep_sky.id, |name| name),
+            Self::MinorBody(minor_body) => minor_body.name.as_deref().unwrap_or(&minor_body.id),
+            Self::Satellite(satellite) => satellite.name.as_deref().unwrap_or(&satellite.id),
+            _ => self.id(),
         }
     }
 
     pub fn constellation(&self) -> Option<&str> {
         match self {
             Self::Star(star) => star.constellation.as_deref(),
+            Self::DeepSky(deep_sky) => deep_sky.constellation.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// ICRS right ascension and declination at J2000.0 in radians for bodies fixed on the sky
+    /// => None for solar system bodies and satellites, their place has to be computed for each instant
+    pub fn catalog_coordinates(&self) -> Option<(f64, f64)> {
+        match self {
+            Self::Star(star) => Some((star.right_ascension, star.declination)),
+            Self::DeepSky(deep_sky) => Some((deep_sky.right_ascension, deep_sky.declination)),
+            _ => None,
+        }
+    }
+
+    /// Geometric geocentric position in AU on the ICRS axes, without light time
+    /// => Only for solar system bodies, `earth` is the Earth's heliocentric position at the same instant
+    pub(crate) fn geocentric_position(
+        &self,
+        julian_ephemeris_day: f64,
+        earth: &[f64; 3],
+    ) -> Option<[f64; 3]> {
+        match self {
+            Self::Planet(planet) => Some(pmp(
+                &planet.heliocentric_position(julian_ephemeris_day),
+                earth,
+            )),
+            Self::MinorBody(minor_body) => Some(pmp(
+                &minor_body.orbit.heliocentric_position(julian_ephemeris_day),
+                earth,
+            )),
+            Self::Moon => Some(moon::moon_geocentric_position(julian_ephemeris_day)),
+            Self::Sun => Some(sxp(-1.0, earth)),
             _ => None,
         }
     }
@@ -80,10 +177,22 @@ impl CelestialBody {
         matches!(self, Self::Star(_))
     }
 
+    pub fn is_deep_sky(&self) -> bool {
+        matches!(self, Self::DeepSky(_))
+    }
+
     pub fn is_planet(&self) -> bool {
         matches!(self, Self::Planet(_))
     }
 
+    pub fn is_minor_body(&self) -> bool {
+        matches!(self, Self::MinorBody(_))
+    }
+
+    pub fn is_satellite(&self) -> bool {
+        matches!(self, Self::Satellite(_))
+    }
+
     pub fn is_moon(&self) -> bool {
         matches!(self, Self::Moon)
     }
@@ -95,33 +204,58 @@ impl CelestialBody {
 
 pub(crate) fn position_from_ecliptic_coords(
     ecliptic_coords: EclPoint,
-    observer: &Observer,
-    time: &Time,
+    distance: f64,
+    astrometry: &Astrometry,
 ) -> Position {
-    let date = time.get_astro_date();
-    let julian_day = astro::time::julian_day(&date);
-
-    let (nut_in_long, nut_in_oblq) = astro::nutation::nutation(julian_day);
-    let mean_obliquity = astro::ecliptic::mn_oblq_IAU(julian_day);
-    let true_obliquity = mean_obliquity + nut_in_oblq;
-
-    let right_ascension =
-        astro::coords::asc_frm_ecl(ecliptic_coords.long, ecliptic_coords.lat, true_obliquity);
-    let declination =
-        astro::coords::dec_frm_ecl(ecliptic_coords.long, ecliptic_coords.lat, true_obliquity);
+    let (right_ascension, declination) =
+        astrometry.ecliptic_to_apparent(ecliptic_coords.long, ecliptic_coords.lat);
+    astrometry.observe_geocentric(right_ascension, declination, distance)
+}
 
-    let mean_sidereal = astro::time::mn_sidr(julian_day);
-    let greenwich_sidereal = astro::time::apprnt_sidr(mean_sidereal, nut_in_long, true_obliquity);
-    let local_sidereal = greenwich_sidereal + observer.longitude;
-    let hour_angle = local_sidereal - right_ascension;
+/// Vector from the viewpoint to a solar system body in AU, corrected for light time
+/// => `heliocentric_position` gives the body's heliocentric position in AU on the ICRS axes at a TT Julian day
+/// => `viewpoint` is heliocentric, in AU and referred to the ICRS axes
+pub(crate) fn astrometric_vector(
+    heliocentric_position: impl Fn(f64) -> [f64; 3],
+    viewpoint: &[f64; 3],
+    julian_ephemeris_day: f64,
+) -> [f64; 3] {
+    let mut astrometric = pmp(&heliocentric_position(julian_ephemeris_day), viewpoint);
+    // Converges to well below a second after a few iterations even for near-Earth objects
+    for _ in 0..3 {
+        let light_time = LIGHT_TIME_PER_AU * pm(astrometric);
+        astrometric = pmp(
+            &heliocentric_position(julian_ephemeris_day - light_time),
+            viewpoint,
+        );
+    }
+    astrometric
+}
 
-    let azimuth = astro::coords::az_frm_eq(hour_angle, declination, observer.latitude);
-    let altitude = astro::coords::alt_frm_eq(hour_angle, declination, observer.latitude);
+/// Heliocentric position of a planet (or the Earth) from VSOP87 in AU, referred to the ICRS axes
+pub(crate) fn vsop87_heliocentric_position(
+    planet: &astro::planet::Planet,
+    julian_ephemeris_day: f64,
+) -> [f64; 3] {
+    let (longitude, latitude, radius) =
+        astro::planet::heliocent_coords(planet, julian_ephemeris_day);
+    ecliptic_of_date_to_icrs(
+        &sxp(radius, &s2c(longitude, latitude)),
+        julian_ephemeris_day,
+    )
+}
 
-    let compass_azimuth = (azimuth.to_degrees() + 180.0) % 360.0;
+/// Rotates a vector from the mean ecliptic and equinox of date, as the astro crate gives them, to the ICRS axes
+/// => Fukushima-Williams precession angles, as in SOFA's ecm06
+pub(crate) fn ecliptic_of_date_to_icrs(ecliptic: &[f64; 3], julian_ephemeris_day: f64) -> [f64; 3] {
+    let (gamma_bar, phi_bar, psi_bar, _) = pfw06(julian_ephemeris_day, 0.0);
+    let mut ecliptic_matrix = [[0.0; 3]; 3];
+    ir(&mut ecliptic_matrix);
+    rz(gamma_bar, &mut ecliptic_matrix);
+    rx(phi_bar, &mut ecliptic_matrix);
+    rz(-psi_bar, &mut ecliptic_matrix);
 
-    Position {
-        azimuth: compass_azimuth,
-        altitude: altitude.to_degrees(),
-    }
+    let mut icrs = [0.0; 3];
+    trxp(&ecliptic_matrix, ecliptic, &mut icrs);
+    icrs
 }
@@ -0,0 +1,126 @@
+use crate::error::CoreResult;
+use crate::position::astrometry::Astrometry;
+use crate::position::earth_orientation::EarthOrientation;
+use crate::position::observer::Observer;
+use crate::position::time::Time;
+use crate::position::Position;
+use bincode::{Decode, Encode};
+use tracing::instrument;
+
+/// Magnitude assumed for objects without a catalogued one
+/// => Faint enough to be treated as barely visible
+pub const UNKNOWN_MAGNITUDE: f64 = 20.0;
+
+/// A galaxy, cluster or nebula from the Messier, NGC or IC catalogs
+/// Data sources:
+/// - https://github.com/mattiaverga/OpenNGC
+#[derive(Debug, Encode, Decode)]
+pub struct DeepSkyObject {
+    /// Primary designation, e.g. "NGC 224" or "IC 434"
+    pub id: String,
+    /// Messier number, if it has one
+    pub messier: Option<u8>,
+    pub common_names: Vec<String>,
+    pub object_type: DeepSkyObjectType,
+    pub constellation: Option<String>,
+    /// Right ascension at J2000.0 epoch in radians
+    pub right_ascension: f64,
+    /// Declination at J2000.0 epoch in radians
+    pub declination: f64,
+    /// Apparent major axis in arcminutes
+    pub major_axis: Option<f64>,
+    /// Apparent minor axis in arcminutes
+    /// => Equal to the major axis for round objects
+    pub minor_axis: Option<f64>,
+    /// Position angle of the major axis in degrees
+    /// => Measured from celestial north through east (0°=N, 90°=E)
+    pub position_angle: Option<f64>,
+    /// Visual magnitude in mag
+    pub visual_magnitude: Option<f64>,
+    /// Blue magnitude in mag
+    /// => Many galaxies only have this one
+    pub blue_magnitude: Option<f64>,
+    /// Mean surface brightness in mag/arcsec²
+    /// => Better than the magnitude at telling how hard an extended object is to see
+    pub surface_brightness: Option<f64>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Encode, Decode)]
+pub enum DeepSkyObjectType {
+    Star,
+    DoubleStar,
+    Association,
+    OpenCluster,
+    GlobularCluster,
+    /// Star cluster with an associated nebula
+    ClusterWithNebula,
+    Galaxy,
+    GalaxyPair,
+    GalaxyTriplet,
+    GalaxyGroup,
+    PlanetaryNebula,
+    HIIRegion,
+    DarkNebula,
+    EmissionNebula,
+    Nebula,
+    ReflectionNebula,
+    SupernovaRemnant,
+    Nova,
+    Other,
+}
+
+impl DeepSkyObjectType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Star => "Star",
+            Self::DoubleStar => "Double star",
+            Self::Association => "Stellar association",
+            Self::OpenCluster => "Open cluster",
+            Self::GlobularCluster => "Globular cluster",
+            Self::ClusterWithNebula => "Cluster with nebula",
+            Self::Galaxy => "Galaxy",
+            Self::GalaxyPair => "Galaxy pair",
+            Self::GalaxyTriplet => "Galaxy triplet",
+            Self::GalaxyGroup => "Group of galaxies",
+            Self::PlanetaryNebula => "Planetary nebula",
+            Self::HIIRegion => "HII region",
+            Self::DarkNebula => "Dark nebula",
+            Self::EmissionNebula => "Emission nebula",
+            Self::Nebula => "Nebula",
+            Self::ReflectionNebula => "Reflection nebula",
+            Self::SupernovaRemnant => "Supernova remnant",
+            Self::Nova => "Nova",
+            Self::Other => "Other",
+        }
+    }
+}
+
+impl DeepSkyObject {
+    #[instrument(skip_all, name = "skyseeker::deep_sky::position")]
+    pub fn position(
+        &self,
+        observer: &Observer,
+        time: &Time,
+        earth_orientation: &EarthOrientation,
+    ) -> CoreResult<Position> {
+        let astrometry = Astrometry::new(observer, time, earth_orientation)?;
+        Ok(self.position_with(&astrometry))
+    }
+
+    /// Deep-sky objects have no measurable proper motion or parallax
+    pub fn position_with(&self, astrometry: &Astrometry) -> Position {
+        astrometry.observe_catalog(self.right_ascension, self.declination, 0.0, 0.0, 0.0, 0.0)
+    }
+
+    /// Visual magnitude, falling back to the blue magnitude and then to UNKNOWN_MAGNITUDE
+    pub fn magnitude(&self) -> f64 {
+        self.visual_magnitude
+            .or(self.blue_magnitude)
+            .unwrap_or(UNKNOWN_MAGNITUDE)
+    }
+
+    /// Messier designation, e.g. "M 31"
+    pub fn messier_id(&self) -> Option<String> {
+        self.messier.map(|messier| format!("M {}", messier))
+    }
+}
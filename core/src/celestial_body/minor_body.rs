@@ -0,0 +1,250 @@
+use crate::celestial_body::{astrometric_vector, LIGHT_TIME_PER_AU};
+use crate::error::{CoreError, CoreResult};
+use crate::position::astrometry::Astrometry;
+use crate::position::earth_orientation::EarthOrientation;
+use crate::position::observer::Observer;
+use crate::position::time::Time;
+use crate::position::Position;
+use bincode::{Decode, Encode};
+use sofars::vm::pm;
+use tracing::instrument;
+
+/// Gaussian gravitational constant in radians per day
+const GAUSSIAN_GRAVITATIONAL_CONSTANT: f64 = 0.01720209895;
+/// Obliquity of the ecliptic at J2000.0 in radians (IAU 2006, 84381.406")
+const J2000_OBLIQUITY: f64 = 84381.406 / 3600.0 * std::f64::consts::PI / 180.0;
+/// Eccentricities closer to 1 than this are treated as parabolic
+const PARABOLIC_TOLERANCE: f64 = 1e-6;
+/// Convergence limit of the Kepler equation solvers in radians
+const KEPLER_PRECISION: f64 = 1e-12;
+
+/// An asteroid or comet following an unperturbed two-body orbit
+/// Data sources:
+/// - https://minorplanetcenter.net/iau/MPCORB/MPCORB.DAT
+/// - https://minorplanetcenter.net/iau/MPCORB/CometEls.txt
+#[derive(Debug, Encode, Decode)]
+pub struct MinorBody {
+    /// Designation, e.g. "(1) Ceres" or "C/2023 A3 (Tsuchinshan-ATLAS)"
+    pub id: String,
+    pub name: Option<String>,
+    pub orbit: OrbitalElements,
+    pub magnitude: MagnitudeParameters,
+}
+
+/// Osculating heliocentric elements referred to the J2000.0 ecliptic and equinox
+/// => Perihelion distance and time replace semi-major axis and mean anomaly
+/// => That way one set of fields describes elliptic, parabolic and hyperbolic orbits
+#[derive(Debug, Clone, Copy, Encode, Decode)]
+pub struct OrbitalElements {
+    /// Epoch of osculation as a Julian Ephemeris Day (TT)
+    /// => The further from it, the larger the error from neglected planetary perturbations
+    pub epoch: f64,
+    /// Time of perihelion passage as a Julian Ephemeris Day (TT)
+    pub perihelion_time: f64,
+    /// Perihelion distance in AU
+    pub perihelion_distance: f64,
+    /// 0 = circle, < 1 = ellipse, 1 = parabola, > 1 = hyperbola
+    pub eccentricity: f64,
+    /// Argument of perihelion in radians
+    pub argument_of_perihelion: f64,
+    /// Longitude of the ascending node in radians
+    pub ascending_node: f64,
+    /// Inclination to the ecliptic in radians
+    pub inclination: f64,
+}
+
+#[derive(Debug, Clone, Copy, Encode, Decode)]
+pub enum MagnitudeParameters {
+    /// IAU H, G system for asteroids
+    /// => m = H + 5 log(r Δ) - 2.5 log((1 - G) Φ1 + G Φ2)
+    Asteroid { absolute_magnitude: f64, slope: f64 },
+    /// Total magnitude of a comet including its coma
+    /// => m = g + 5 log Δ + 2.5 k log r
+    Comet { absolute_magnitude: f64, slope: f64 },
+}
+
+/// Brightness and geometry of a minor body as seen from the Earth's center
+#[derive(Debug, Clone, Copy)]
+pub struct MinorBodyAppearance {
+    /// Apparent visual magnitude
+    pub visual_magnitude: f64,
+    /// Sun-body-Earth angle in degrees
+    pub phase_angle: f64,
+    /// Distance to the Sun in AU
+    pub sun_distance: f64,
+    /// Distance to the Earth in AU
+    pub earth_distance: f64,
+}
+
+impl OrbitalElements {
+    /// Elements of an elliptic orbit given with mean anomaly and semi-major axis, as in MPCORB
+    /// => Angles in radians, semi-major axis in AU, epoch as a Julian Ephemeris Day (TT)
+    pub fn from_mean_anomaly(
+        epoch: f64,
+        mean_anomaly: f64,
+        semi_major_axis: f64,
+        eccentricity: f64,
+        argument_of_perihelion: f64,
+        ascending_node: f64,
+        inclination: f64,
+    ) -> Self {
+        let mean_motion = GAUSSIAN_GRAVITATIONAL_CONSTANT / semi_major_axis.powf(1.5);
+        Self {
+            epoch,
+            perihelion_time: epoch - mean_anomaly / mean_motion,
+            perihelion_distance: semi_major_axis * (1.0 - eccentricity),
+            eccentricity,
+            argument_of_perihelion,
+            ascending_node,
+            inclination,
+        }
+    }
+
+    /// Heliocentric position in AU, referred to the ICRS (J2000 equatorial) axes
+    pub fn heliocentric_position(&self, julian_ephemeris_day: f64) -> [f64; 3] {
+        let (true_anomaly, radius) = self.true_anomaly_and_radius(julian_ephemeris_day);
+
+        let argument_of_latitude = self.argument_of_perihelion + true_anomaly;
+        let (sin_u, cos_u) = argument_of_latitude.sin_cos();
+        let (sin_node, cos_node) = self.ascending_node.sin_cos();
+        let (sin_i, cos_i) = self.inclination.sin_cos();
+
+        let x = radius * (cos_node * cos_u - sin_node * sin_u * cos_i);
+        let y = radius * (sin_node * cos_u + cos_node * sin_u * cos_i);
+        let z = radius * sin_u * sin_i;
+
+        let (sin_e, cos_e) = J2000_OBLIQUITY.sin_cos();
+        [x, y * cos_e - z * sin_e, y * sin_e + z * cos_e]
+    }
+
+    /// True anomaly in radians and distance from the Sun in AU
+    fn true_anomaly_and_radius(&self, julian_ephemeris_day: f64) -> (f64, f64) {
+        let q = self.perihelion_distance;
+        let e = self.eccentricity;
+        let days = julian_ephemeris_day - self.perihelion_time;
+        let k = GAUSSIAN_GRAVITATIONAL_CONSTANT;
+
+        if (e - 1.0).abs() < PARABOLIC_TOLERANCE {
+            // Barker's equation, solved in closed form
+            let w = 3.0 * k / (2.0 * q.powi(3)).sqrt() * days;
+            let y = (w / 2.0 + (w * w / 4.0 + 1.0).sqrt()).cbrt();
+            let s = y - 1.0 / y;
+            (2.0 * s.atan(), q * (1.0 + s * s))
+        } else if e < 1.0 {
+            let a = q / (1.0 - e);
+            let mean_anomaly = (k / a.powf(1.5) * days).rem_euclid(std::f64::consts::TAU);
+            let mut eccentric_anomaly = if e > 0.8 {
+                std::f64::consts::PI
+            } else {
+                mean_anomaly
+            };
+            for _ in 0..100 {
+                let delta = (eccentric_anomaly - e * eccentric_anomaly.sin() - mean_anomaly)
+                    / (1.0 - e * eccentric_anomaly.cos());
+                eccentric_anomaly -= delta;
+                if delta.abs() < KEPLER_PRECISION {
+                    break;
+                }
+            }
+            let true_anomaly = 2.0
+                * ((1.0 + e).sqrt() * (eccentric_anomaly / 2.0).sin())
+                    .atan2((1.0 - e).sqrt() * (eccentric_anomaly / 2.0).cos());
+            (true_anomaly, a * (1.0 - e * eccentric_anomaly.cos()))
+        } else {
+            let a = q / (e - 1.0);
+            let mean_anomaly = k / a.powf(1.5) * days;
+            let mut hyperbolic_anomaly = (2.0 * mean_anomaly / e).asinh();
+            for _ in 0..100 {
+                let delta = (e * hyperbolic_anomaly.sinh() - hyperbolic_anomaly - mean_anomaly)
+                    / (e * hyperbolic_anomaly.cosh() - 1.0);
+                hyperbolic_anomaly -= delta;
+                if delta.abs() < KEPLER_PRECISION {
+                    break;
+                }
+            }
+            let true_anomaly =
+                2.0 * (((e + 1.0) / (e - 1.0)).sqrt() * (hyperbolic_anomaly / 2.0).tanh()).atan();
+            (true_anomaly, a * (e * hyperbolic_anomaly.cosh() - 1.0))
+        }
+    }
+}
+
+impl MinorBody {
+    #[instrument(skip_all, name = "skyseeker::minor_body::position")]
+    pub fn position(
+        &self,
+        observer: &Observer,
+        time: &Time,
+        earth_orientation: &EarthOrientation,
+    ) -> CoreResult<Position> {
+        let astrometry = Astrometry::new(observer, time, earth_orientation)?;
+        Ok(self.position_with(&astrometry))
+    }
+
+    pub fn position_with(&self, astrometry: &Astrometry) -> Position {
+        let astrometric = astrometric_vector(
+            |julian_ephemeris_day| self.orbit.heliocentric_position(julian_ephemeris_day),
+            &astrometry.observer_heliocentric(),
+            astrometry.julian_ephemeris_day(),
+        );
+        astrometry.observe_topocentric(&astrometric)
+    }
+
+    /// Brightness and geometry at `time` as seen from the Earth's center
+    /// => Fails outside the years 1900 to 2100 covered by the Earth ephemeris
+    pub fn appearance(&self, time: &Time) -> CoreResult<MinorBodyAppearance> {
+        let julian_ephemeris_day = time.get_julian_ephemeris_day()?;
+        let Some(([earth, _], _)) = sofars::eph::epv00(julian_ephemeris_day, 0.0) else {
+            return Err(CoreError::EarthEphemerisDate);
+        };
+
+        let geocentric = astrometric_vector(
+            |julian_ephemeris_day| self.orbit.heliocentric_position(julian_ephemeris_day),
+            &earth,
+            julian_ephemeris_day,
+        );
+        let earth_distance = pm(geocentric);
+        let light_time = LIGHT_TIME_PER_AU * earth_distance;
+        let sun_distance = pm(self
+            .orbit
+            .heliocentric_position(julian_ephemeris_day - light_time));
+        let earth_sun_distance = pm(earth);
+
+        let cos_phase_angle = (sun_distance.powi(2) + earth_distance.powi(2)
+            - earth_sun_distance.powi(2))
+            / (2.0 * sun_distance * earth_distance);
+        let phase_angle = cos_phase_angle.clamp(-1.0, 1.0).acos();
+
+        let visual_magnitude = match self.magnitude {
+            MagnitudeParameters::Asteroid {
+                absolute_magnitude,
+                slope,
+            } => {
+                let half_tan = (phase_angle / 2.0).tan();
+                let phi_1 = (-3.33 * half_tan.powf(0.63)).exp();
+                let phi_2 = (-1.87 * half_tan.powf(1.22)).exp();
+                absolute_magnitude + 5.0 * (sun_distance * earth_distance).log10()
+                    - 2.5 * ((1.0 - slope) * phi_1 + slope * phi_2).log10()
+            }
+            MagnitudeParameters::Comet {
+                absolute_magnitude,
+                slope,
+            } => {
+                absolute_magnitude
+                    + 5.0 * earth_distance.log10()
+                    + 2.5 * slope * sun_distance.log10()
+            }
+        };
+
+        Ok(MinorBodyAppearance {
+            visual_magnitude,
+            phase_angle: phase_angle.to_degrees(),
+            sun_distance,
+            earth_distance,
+        })
+    }
+
+    pub fn is_comet(&self) -> bool {
+        matches!(self.magnitude, MagnitudeParameters::Comet { .. })
+    }
+}
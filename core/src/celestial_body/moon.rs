@@ -1,11 +1,242 @@
-use crate::celestial_body::position_from_ecliptic_coords;
-use crate::position::observer::Observer;
+use crate::celestial_body::{ecliptic_of_date_to_icrs, position_from_ecliptic_coords};
+use crate::error::CoreResult;
+use crate::position::astrometry::Astrometry;
 use crate::position::time::Time;
 use crate::position::Position;
+use astro::coords::EqPoint;
+use astro::lunar::Phase;
+use sofars::vm::{s2c, sxp};
 use tracing::instrument;
 
+/// Mean length of a lunation in days
+const SYNODIC_MONTH: f64 = 29.530588861;
+/// Mean Earth-Moon distance in km
+const MEAN_EARTH_MOON_DISTANCE: f64 = 384400.0;
+/// Astronomical unit in km
+const ASTRONOMICAL_UNIT: f64 = 149597870.7;
+
 #[instrument(skip_all, name = "skyseeker::moon::position")]
-pub fn moon_position(observer: &Observer, time: &Time) -> Position {
-    let (ecliptic_coords, _) = astro::lunar::geocent_ecl_pos(time.get_astro_julian_day());
-    position_from_ecliptic_coords(ecliptic_coords, observer, time)
+pub fn moon_position(astrometry: &Astrometry) -> Position {
+    let (ecliptic_coords, earth_moon_distance) =
+        astro::lunar::geocent_ecl_pos(astrometry.julian_ephemeris_day());
+    position_from_ecliptic_coords(
+        ecliptic_coords,
+        earth_moon_distance / ASTRONOMICAL_UNIT,
+        astrometry,
+    )
+}
+
+/// Geometric geocentric position of the Moon in AU, referred to the ICRS axes
+pub(crate) fn moon_geocentric_position(julian_ephemeris_day: f64) -> [f64; 3] {
+    let (ecliptic_coords, earth_moon_distance) =
+        astro::lunar::geocent_ecl_pos(julian_ephemeris_day);
+    ecliptic_of_date_to_icrs(
+        &sxp(
+            earth_moon_distance / ASTRONOMICAL_UNIT,
+            &s2c(ecliptic_coords.long, ecliptic_coords.lat),
+        ),
+        julian_ephemeris_day,
+    )
+}
+
+/// Illumination and orientation of the Moon as seen from the Earth's center
+#[derive(Debug, Clone, Copy)]
+pub struct MoonPhase {
+    /// Illuminated fraction of the disk
+    /// => 0.0 = new moon, 1.0 = full moon
+    pub illuminated_fraction: f64,
+    /// Sun-Moon-Earth angle in degrees
+    /// => 180° = new moon, 0° = full moon
+    pub phase_angle: f64,
+    pub phase_name: MoonPhaseName,
+    /// Days since the last new moon
+    pub age: f64,
+    /// Position angle of the midpoint of the bright limb in degrees
+    /// => Measured from celestial north through east (0°=N, 90°=E)
+    pub bright_limb_angle: f64,
+    /// Total (optical + physical) libration in longitude in degrees
+    /// => Positive values turn the eastern limb (Mare Crisium) towards the Earth
+    pub libration_longitude: f64,
+    /// Total (optical + physical) libration in latitude in degrees
+    /// => Positive values turn the northern limb towards the Earth
+    pub libration_latitude: f64,
+    /// Apparent visual magnitude
+    pub visual_magnitude: f64,
+    pub next_new_moon: Time,
+    pub next_first_quarter: Time,
+    pub next_full_moon: Time,
+    pub next_last_quarter: Time,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoonPhaseName {
+    NewMoon,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    FullMoon,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+impl MoonPhaseName {
+    /// Phase name from the Moon's elongation east of the Sun in degrees
+    /// => Each name covers a 45° sector centered on its nominal elongation
+    pub fn from_elongation(elongation: f64) -> Self {
+        match ((elongation.rem_euclid(360.0) + 22.5) / 45.0) as u32 % 8 {
+            0 => Self::NewMoon,
+            1 => Self::WaxingCrescent,
+            2 => Self::FirstQuarter,
+            3 => Self::WaxingGibbous,
+            4 => Self::FullMoon,
+            5 => Self::WaningGibbous,
+            6 => Self::LastQuarter,
+            _ => Self::WaningCrescent,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::NewMoon => "New Moon",
+            Self::WaxingCrescent => "Waxing Crescent",
+            Self::FirstQuarter => "First Quarter",
+            Self::WaxingGibbous => "Waxing Gibbous",
+            Self::FullMoon => "Full Moon",
+            Self::WaningGibbous => "Waning Gibbous",
+            Self::LastQuarter => "Last Quarter",
+            Self::WaningCrescent => "Waning Crescent",
+        }
+    }
+}
+
+#[instrument(skip_all, name = "skyseeker::moon::phase")]
+pub fn moon_phase(time: &Time) -> CoreResult<MoonPhase> {
+    let julian_day = time.get_astro_julian_day();
+    let (moon_ecliptic, earth_moon_distance) = astro::lunar::geocent_ecl_pos(julian_day);
+    let (sun_ecliptic, earth_sun_distance) = astro::sun::geocent_ecl_pos(julian_day);
+
+    let phase_angle = phase_angle(
+        moon_ecliptic.long,
+        moon_ecliptic.lat,
+        sun_ecliptic.long,
+        earth_moon_distance,
+        earth_sun_distance * ASTRONOMICAL_UNIT,
+    );
+    let illuminated_fraction = (1.0 + phase_angle.cos()) / 2.0;
+    let elongation = (moon_ecliptic.long - sun_ecliptic.long).to_degrees();
+
+    let obliquity = astro::ecliptic::mn_oblq_IAU(julian_day);
+    let to_equatorial = |long: f64, lat: f64| EqPoint {
+        asc: astro::coords::asc_frm_ecl(long, lat, obliquity),
+        dec: astro::coords::dec_frm_ecl(long, lat, obliquity),
+    };
+    let bright_limb_angle = astro::lunar::bright_limb(
+        to_equatorial(sun_ecliptic.long, sun_ecliptic.lat),
+        to_equatorial(moon_ecliptic.long, moon_ecliptic.lat),
+    );
+
+    // astro::lunar::total_libr passes its arguments in the wrong order, so the parts are summed here
+    let (optical_longitude, optical_latitude) =
+        astro::lunar::optical_libr(julian_day, moon_ecliptic.long, moon_ecliptic.lat);
+    let (physical_longitude, physical_latitude) = astro::lunar::physical_libr(
+        julian_day,
+        moon_ecliptic.long,
+        moon_ecliptic.lat,
+        optical_latitude,
+    );
+
+    let previous_new_moon = previous_phase_time(time, &Phase::New)?;
+
+    Ok(MoonPhase {
+        illuminated_fraction,
+        phase_angle: phase_angle.to_degrees(),
+        phase_name: MoonPhaseName::from_elongation(elongation),
+        age: time.get_julian_day()? - previous_new_moon.get_julian_day()?,
+        bright_limb_angle: bright_limb_angle.to_degrees().rem_euclid(360.0),
+        libration_longitude: signed_degrees(optical_longitude + physical_longitude),
+        libration_latitude: signed_degrees(optical_latitude + physical_latitude),
+        visual_magnitude: moon_magnitude(phase_angle.to_degrees(), earth_moon_distance),
+        next_new_moon: next_phase_time(time, &Phase::New)?,
+        next_first_quarter: next_phase_time(time, &Phase::First)?,
+        next_full_moon: next_phase_time(time, &Phase::Full)?,
+        next_last_quarter: next_phase_time(time, &Phase::Last)?,
+    })
+}
+
+/// Apparent visual magnitude of the Moon at the given time
+pub fn moon_visual_magnitude(time: &Time) -> f64 {
+    let julian_day = time.get_astro_julian_day();
+    let (moon_ecliptic, earth_moon_distance) = astro::lunar::geocent_ecl_pos(julian_day);
+    let (sun_ecliptic, earth_sun_distance) = astro::sun::geocent_ecl_pos(julian_day);
+    let phase_angle = phase_angle(
+        moon_ecliptic.long,
+        moon_ecliptic.lat,
+        sun_ecliptic.long,
+        earth_moon_distance,
+        earth_sun_distance * ASTRONOMICAL_UNIT,
+    );
+    moon_magnitude(phase_angle.to_degrees(), earth_moon_distance)
+}
+
+/// Sun-Moon-Earth angle in radians
+/// => Both distances have to be in the same unit
+fn phase_angle(
+    moon_long: f64,
+    moon_lat: f64,
+    sun_long: f64,
+    earth_moon_distance: f64,
+    earth_sun_distance: f64,
+) -> f64 {
+    let geocentric_elongation = (moon_lat.cos() * (moon_long - sun_long).cos()).acos();
+    (earth_sun_distance * geocentric_elongation.sin())
+        .atan2(earth_moon_distance - earth_sun_distance * geocentric_elongation.cos())
+}
+
+/// Visual magnitude from the phase angle in degrees (Allen, Astrophysical Quantities)
+/// => Corrected from the mean to the actual Earth-Moon distance in km
+fn moon_magnitude(phase_angle: f64, earth_moon_distance: f64) -> f64 {
+    let phase_angle = phase_angle.abs();
+    -12.73
+        + 0.026 * phase_angle
+        + 4e-9 * phase_angle.powi(4)
+        + 5.0 * (earth_moon_distance / MEAN_EARTH_MOON_DISTANCE).log10()
+}
+
+fn signed_degrees(radians: f64) -> f64 {
+    (radians.to_degrees() + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Time of the given phase in UTC
+/// => astro::lunar::time_of_phase returns the phase of the lunation containing `date` in TT
+fn phase_time(date: &Time, phase: &Phase) -> CoreResult<Time> {
+    let ephemeris_day = astro::lunar::time_of_phase(&date.get_astro_date(), phase);
+    let delta_t = astro::time::delta_t(date.year, date.month as u8);
+    Time::from_julian_day(ephemeris_day - delta_t / 86400.0)
+}
+
+pub(crate) fn next_phase_time(time: &Time, phase: &Phase) -> CoreResult<Time> {
+    let julian_day = time.get_julian_day()?;
+    let mut date = *time;
+    for _ in 0..3 {
+        let candidate = phase_time(&date, phase)?;
+        if candidate.get_julian_day()? > julian_day {
+            return Ok(candidate);
+        }
+        date = date.add_days(SYNODIC_MONTH / 2.0)?;
+    }
+    phase_time(&date, phase)
+}
+
+fn previous_phase_time(time: &Time, phase: &Phase) -> CoreResult<Time> {
+    let julian_day = time.get_julian_day()?;
+    let mut date = *time;
+    for _ in 0..3 {
+        let candidate = phase_time(&date, phase)?;
+        if candidate.get_julian_day()? <= julian_day {
+            return Ok(candidate);
+        }
+        date = date.add_days(-SYNODIC_MONTH / 2.0)?;
+    }
+    phase_time(&date, phase)
 }
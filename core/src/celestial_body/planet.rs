@@ -1,4 +1,7 @@
-use crate::celestial_body::position_from_ecliptic_coords;
+use crate::celestial_body::{astrometric_vector, vsop87_heliocentric_position, LIGHT_TIME_PER_AU};
+use crate::error::CoreResult;
+use crate::position::astrometry::Astrometry;
+use crate::position::earth_orientation::EarthOrientation;
 use crate::position::observer::Observer;
 use crate::position::time::Time;
 use crate::position::Position;
@@ -41,12 +44,151 @@ impl Planet {
         }
     }
 
+    /// Equatorial semidiameter at a distance of 1 AU in arcseconds
+    pub fn unit_semidiameter(&self) -> f64 {
+        match self {
+            Planet::Mercury => 3.36,
+            Planet::Venus => 8.41,
+            Planet::Mars => 4.68,
+            Planet::Jupiter => 98.44,
+            Planet::Saturn => 82.73,
+            Planet::Uranus => 35.02,
+            Planet::Neptune => 33.50,
+        }
+    }
+
+    #[instrument(skip_all, name = "skyseeker::planet::appearance")]
+    pub fn appearance(&self, time: &Time) -> PlanetAppearance {
+        let julian_day = time.get_astro_julian_day();
+        let (ecliptic_coords, earth_distance) =
+            astro::planet::geocent_apprnt_ecl_coords(&self.get_astro_planet(), julian_day);
+        let light_time = LIGHT_TIME_PER_AU * earth_distance;
+        let (_, _, sun_distance) =
+            astro::planet::heliocent_coords(&self.get_astro_planet(), julian_day - light_time);
+        let (_, _, earth_sun_distance) =
+            astro::planet::heliocent_coords(&astro::planet::Planet::Earth, julian_day);
+
+        let cos_phase_angle = (sun_distance.powi(2) + earth_distance.powi(2)
+            - earth_sun_distance.powi(2))
+            / (2.0 * sun_distance * earth_distance);
+        let phase_angle = cos_phase_angle.clamp(-1.0, 1.0).acos().to_degrees();
+
+        let ring_tilt = match self {
+            Planet::Saturn => Some(saturn_ring_tilt(
+                julian_day,
+                ecliptic_coords.long,
+                ecliptic_coords.lat,
+            )),
+            _ => None,
+        };
+
+        let distance_modulus = 5.0 * (sun_distance * earth_distance).log10();
+        let visual_magnitude =
+            distance_modulus + self.unit_magnitude(phase_angle, ring_tilt.unwrap_or(0.0));
+
+        PlanetAppearance {
+            visual_magnitude,
+            angular_diameter: 2.0 * self.unit_semidiameter() / earth_distance,
+            illuminated_fraction: (1.0 + phase_angle.to_radians().cos()) / 2.0,
+            phase_angle,
+            sun_distance,
+            earth_distance,
+            ring_tilt,
+        }
+    }
+
+    /// Magnitude at 1 AU from both Sun and Earth for the given phase angle in degrees
+    /// => Mallama & Hilton (2018), as adopted by the Astronomical Almanac
+    /// => The ring tilt in degrees is only used for Saturn
+    fn unit_magnitude(&self, phase_angle: f64, ring_tilt: f64) -> f64 {
+        let i = phase_angle;
+        match self {
+            Planet::Mercury => {
+                -0.613
+                    + i * (6.3280e-2
+                        + i * (-1.6336e-3
+                            + i * (3.3644e-5
+                                + i * (-3.4265e-7 + i * (1.6893e-9 - i * 3.0334e-12)))))
+            }
+            Planet::Venus if i <= 163.7 => {
+                -4.384 + i * (-1.044e-3 + i * (3.687e-4 + i * (-2.814e-6 + i * 8.938e-9)))
+            }
+            Planet::Venus => 236.05828 + i * (-2.81914 + i * 8.39034e-3),
+            Planet::Mars if i <= 50.0 => -1.601 + i * (2.267e-2 - i * 1.302e-4),
+            Planet::Mars => -0.367 + i * (-2.573e-2 + i * 3.445e-4),
+            Planet::Jupiter if i <= 12.0 => -9.395 + i * (-3.7e-4 + i * 6.16e-4),
+            Planet::Jupiter => {
+                let x = i / 180.0;
+                -9.428
+                    - 2.5
+                        * (1.0
+                            + x * (-1.507 + x * (-0.363 + x * (-0.062 + x * (2.809 - x * 1.876)))))
+                            .log10()
+            }
+            Planet::Saturn => {
+                let sin_tilt = ring_tilt.to_radians().abs().sin();
+                -8.914 - 1.825 * sin_tilt + 0.026 * i - 0.378 * sin_tilt * (-2.25 * i).exp()
+            }
+            Planet::Uranus => -7.110 + i * (6.587e-3 + i * 1.045e-4),
+            Planet::Neptune => -7.00,
+        }
+    }
+
     #[instrument(skip_all, name = "skyseeker::planet::position")]
-    pub fn position(&self, observer: &Observer, time: &Time) -> Position {
-        let (ecliptic_coords, _) = astro::planet::geocent_apprnt_ecl_coords(
-            &self.get_astro_planet(),
-            time.get_astro_julian_day(),
+    pub fn position(
+        &self,
+        observer: &Observer,
+        time: &Time,
+        earth_orientation: &EarthOrientation,
+    ) -> CoreResult<Position> {
+        let astrometry = Astrometry::new(observer, time, earth_orientation)?;
+        Ok(self.position_with(&astrometry))
+    }
+
+    pub fn position_with(&self, astrometry: &Astrometry) -> Position {
+        let astrometric = astrometric_vector(
+            |julian_ephemeris_day| self.heliocentric_position(julian_ephemeris_day),
+            &astrometry.observer_heliocentric(),
+            astrometry.julian_ephemeris_day(),
         );
-        position_from_ecliptic_coords(ecliptic_coords, observer, time)
+        astrometry.observe_topocentric(&astrometric)
     }
+
+    /// Heliocentric position in AU, referred to the ICRS axes
+    pub fn heliocentric_position(&self, julian_ephemeris_day: f64) -> [f64; 3] {
+        vsop87_heliocentric_position(&self.get_astro_planet(), julian_ephemeris_day)
+    }
+}
+
+/// Brightness and apparent disk of a planet as seen from the Earth's center
+#[derive(Debug, Clone, Copy)]
+pub struct PlanetAppearance {
+    /// Apparent visual magnitude
+    pub visual_magnitude: f64,
+    /// Apparent equatorial diameter in arcseconds
+    /// => For Saturn this is the globe only, without the rings
+    pub angular_diameter: f64,
+    /// Illuminated fraction of the disk
+    /// => 1.0 = fully lit, as for the outer planets at opposition
+    pub illuminated_fraction: f64,
+    /// Sun-planet-Earth angle in degrees
+    pub phase_angle: f64,
+    /// Sun-planet distance in AU
+    pub sun_distance: f64,
+    /// Earth-planet distance in AU
+    pub earth_distance: f64,
+    /// Saturnicentric latitude of the Earth referred to the ring plane in degrees
+    /// => Only set for Saturn, 0° = rings seen edge-on
+    pub ring_tilt: Option<f64>,
+}
+
+/// Tilt of Saturn's rings towards the Earth in degrees (Meeus, chapter 45)
+fn saturn_ring_tilt(julian_day: f64, ecliptic_long: f64, ecliptic_lat: f64) -> f64 {
+    let julian_century = astro::time::julian_cent(julian_day);
+    let inclination = astro::planet::saturn::ring::inc(julian_century);
+    let ascending_node = astro::planet::saturn::ring::ascend_node(julian_century);
+    (inclination.sin() * ecliptic_lat.cos() * (ecliptic_long - ascending_node).sin()
+        - inclination.cos() * ecliptic_lat.sin())
+    .asin()
+    .to_degrees()
 }
@@ -0,0 +1,213 @@
+use crate::error::{CoreError, CoreResult};
+use crate::position::astrometry::Astrometry;
+use crate::position::earth_orientation::EarthOrientation;
+use crate::position::observer::Observer;
+use crate::position::time::Time;
+use crate::position::Position;
+use bincode::de::Decoder;
+use bincode::enc::Encoder;
+use bincode::error::{DecodeError, EncodeError};
+use bincode::{Decode, Encode};
+use sofars::vm::{c2s, pdp, pm, pmp};
+use std::sync::OnceLock;
+use tracing::instrument;
+
+/// Magnitude used for satellites, TLEs carry no brightness information
+pub const NOMINAL_MAGNITUDE: f64 = 4.0;
+/// Astronomical unit in km
+const ASTRONOMICAL_UNIT: f64 = 149597870.7;
+/// Equatorial radius of the Earth in km (WGS72, as used by SGP4)
+const EARTH_RADIUS: f64 = 6378.135;
+/// Rotation rate of the Earth in radians per second
+const EARTH_ROTATION_RATE: f64 = 7.292115e-5;
+/// Days from the J2000.0 epoch per Julian year
+const DAYS_PER_JULIAN_YEAR: f64 = 365.25;
+const J2000: f64 = 2451545.0;
+
+/// An artificial satellite described by a two-line element set
+/// Data sources:
+/// - https://celestrak.org/NORAD/elements/
+#[derive(Debug, Encode, Decode)]
+pub struct Satellite {
+    /// Name from the title line, or "NORAD <catalog number>" without one
+    pub id: String,
+    pub name: Option<String>,
+    /// USSPACECOM catalog number
+    pub norad_id: u64,
+    /// The two element lines, kept verbatim
+    /// => SGP4 needs the exact mean elements they encode, so the model is built from them on first use
+    pub line1: String,
+    pub line2: String,
+    propagator: PropagatorCell,
+}
+
+/// SGP4 model of the element set, ready to propagate
+#[derive(Debug)]
+struct Propagator {
+    /// Epoch of the elements as a UTC Julian day
+    epoch: f64,
+    constants: sgp4::Constants,
+}
+
+/// The propagator, set up when the satellite is first placed
+/// => Encodes to nothing, a decoded satellite sets it up again from its element lines
+#[derive(Debug, Default)]
+struct PropagatorCell(OnceLock<Propagator>);
+
+impl Encode for PropagatorCell {
+    fn encode<E: Encoder>(&self, _encoder: &mut E) -> Result<(), EncodeError> {
+        Ok(())
+    }
+}
+
+impl<Context> Decode<Context> for PropagatorCell {
+    fn decode<D: Decoder<Context = Context>>(_decoder: &mut D) -> Result<Self, DecodeError> {
+        Ok(Self::default())
+    }
+}
+
+bincode::impl_borrow_decode!(PropagatorCell);
+
+/// Where a satellite is as seen by an observer
+#[derive(Debug, Clone, Copy)]
+pub struct SatelliteState {
+    pub position: Position,
+    /// Distance from the observer in km
+    pub range: f64,
+    /// Rate of change of the range in km/s
+    /// => Positive when the satellite moves away from the observer
+    pub range_rate: f64,
+    /// Whether the satellite is lit by the Sun
+    /// => Outside the Earth's shadow, modelled as a cylinder
+    pub sunlit: bool,
+}
+
+impl Satellite {
+    /// Builds a satellite from a two-line element set, checking that SGP4 accepts it
+    pub fn from_tle(name: Option<String>, line1: &str, line2: &str) -> CoreResult<Self> {
+        let elements = sgp4::Elements::from_tle(name.clone(), line1.as_bytes(), line2.as_bytes())
+            .map_err(|e| CoreError::SatelliteElements(e.to_string()))?;
+        let propagator = Propagator::new(&elements)?;
+
+        Ok(Self {
+            id: name
+                .clone()
+                .unwrap_or_else(|| format!("NORAD {}", elements.norad_id)),
+            name,
+            norad_id: elements.norad_id,
+            line1: line1.to_string(),
+            line2: line2.to_string(),
+            propagator: PropagatorCell(OnceLock::from(propagator)),
+        })
+    }
+
+    #[instrument(skip_all, name = "skyseeker::satellite::position")]
+    pub fn position(
+        &self,
+        observer: &Observer,
+        time: &Time,
+        earth_orientation: &EarthOrientation,
+    ) -> CoreResult<Position> {
+        let astrometry = Astrometry::new(observer, time, earth_orientation)?;
+        self.position_with(&astrometry)
+    }
+
+    pub fn position_with(&self, astrometry: &Astrometry) -> CoreResult<Position> {
+        Ok(self.state_with(astrometry)?.position)
+    }
+
+    pub fn state(
+        &self,
+        observer: &Observer,
+        time: &Time,
+        earth_orientation: &EarthOrientation,
+    ) -> CoreResult<SatelliteState> {
+        let astrometry = Astrometry::new(observer, time, earth_orientation)?;
+        self.state_with(&astrometry)
+    }
+
+    /// Propagates the elements with SGP4/SDP4 and places the satellite for the observer
+    /// => SGP4 works in the TEME frame, which is treated as the true equator and equinox of date
+    /// => They differ by the equation of the equinoxes (< 1.2s in right ascension), far below the accuracy of TLEs
+    pub fn state_with(&self, astrometry: &Astrometry) -> CoreResult<SatelliteState> {
+        let prediction = self.propagate(astrometry.julian_day())?;
+
+        let observer = astrometry
+            .observer_equatorial()
+            .map(|meters| meters / 1000.0);
+        let observer_velocity = [
+            -EARTH_ROTATION_RATE * observer[1],
+            EARTH_ROTATION_RATE * observer[0],
+            0.0,
+        ];
+        let topocentric = pmp(&prediction.position, &observer);
+        let relative_velocity = pmp(&prediction.velocity, &observer_velocity);
+        let range = pm(topocentric);
+
+        let (right_ascension, declination) = c2s(&prediction.position);
+        let position = astrometry.observe_geocentric(
+            right_ascension,
+            declination,
+            pm(prediction.position) / ASTRONOMICAL_UNIT,
+        );
+
+        Ok(SatelliteState {
+            position,
+            range,
+            range_rate: pdp(&topocentric, &relative_velocity) / range,
+            sunlit: is_sunlit(&prediction.position, astrometry.julian_ephemeris_day()),
+        })
+    }
+
+    /// Geocentric position in km and velocity in km/s in the TEME frame at a UTC Julian day
+    pub(crate) fn propagate(&self, julian_day: f64) -> CoreResult<sgp4::Prediction> {
+        let propagator = self.propagator()?;
+        let minutes_since_epoch = (julian_day - propagator.epoch) * 1440.0;
+        propagator
+            .constants
+            .propagate(sgp4::MinutesSinceEpoch(minutes_since_epoch))
+            .map_err(|e| CoreError::SatellitePropagation(e.to_string()))
+    }
+
+    /// Parsing the elements and setting up SGP4 is several times the cost of propagating, so it is done once
+    fn propagator(&self) -> CoreResult<&Propagator> {
+        if let Some(propagator) = self.propagator.0.get() {
+            return Ok(propagator);
+        }
+        let elements = sgp4::Elements::from_tle(
+            self.name.clone(),
+            self.line1.as_bytes(),
+            self.line2.as_bytes(),
+        )
+        .map_err(|e| CoreError::SatelliteElements(e.to_string()))?;
+        let propagator = Propagator::new(&elements)?;
+        Ok(self.propagator.0.get_or_init(|| propagator))
+    }
+}
+
+impl Propagator {
+    fn new(elements: &sgp4::Elements) -> CoreResult<Self> {
+        Ok(Self {
+            epoch: J2000 + elements.epoch() * DAYS_PER_JULIAN_YEAR,
+            constants: sgp4::Constants::from_elements(elements)
+                .map_err(|e| CoreError::SatelliteElements(e.to_string()))?,
+        })
+    }
+}
+
+/// Whether a geocentric position in km is outside the Earth's shadow
+fn is_sunlit(position: &[f64; 3], julian_ephemeris_day: f64) -> bool {
+    let (sun_ecliptic, _) = astro::sun::geocent_ecl_pos(julian_ephemeris_day);
+    let obliquity = astro::ecliptic::mn_oblq_IAU(julian_ephemeris_day);
+    let sun_direction = sofars::vm::s2c(
+        astro::coords::asc_frm_ecl(sun_ecliptic.long, sun_ecliptic.lat, obliquity),
+        astro::coords::dec_frm_ecl(sun_ecliptic.long, sun_ecliptic.lat, obliquity),
+    );
+
+    let along_sun = pdp(position, &sun_direction);
+    if along_sun >= 0.0 {
+        return true;
+    }
+    let off_axis = (pdp(position, position) - along_sun * along_sun).sqrt();
+    off_axis > EARTH_RADIUS
+}
@@ -1,10 +1,10 @@
-use crate::error::{CoreError, CoreResult};
+use crate::error::CoreResult;
+use crate::position::astrometry::Astrometry;
 use crate::position::earth_orientation::EarthOrientation;
 use crate::position::observer::Observer;
 use crate::position::time::Time;
 use crate::position::Position;
 use bincode::{Decode, Encode};
-use sofars::astro::atco13;
 use tracing::instrument;
 
 /// Data sources:
@@ -57,38 +57,26 @@ impl Star {
         time: &Time,
         earth_orientation: &EarthOrientation,
     ) -> CoreResult<Position> {
-        let (utc1, utc2) = time.get_double_julian()?;
+        let astrometry = Astrometry::new(observer, time, earth_orientation)?;
+        Ok(self.position_with(&astrometry))
+    }
 
-        match atco13(
+    pub fn position_with(&self, astrometry: &Astrometry) -> Position {
+        astrometry.observe_catalog(
             self.right_ascension,
             self.declination,
             self.proper_motion_right_ascension,
             self.proper_motion_declination,
             self.parallax,
             self.radial_velocity,
-            utc1,
-            utc2,
-            earth_orientation.dut1,
-            observer.longitude,
-            observer.latitude,
-            observer.height(),
-            earth_orientation.polar_motion_x(),
-            earth_orientation.polar_motion_y(),
-            observer.pressure(),
-            observer.temperature(),
-            observer.humidity(),
-            observer.wavelength(),
-        ) {
-            Ok((azimuth, zenith_dist, _, _, _, _)) => {
-                let azimuth_deg = azimuth.to_degrees();
-                let zenith_dist_deg = zenith_dist.to_degrees();
-                let altitude_deg = 90.0 - zenith_dist_deg;
-                Ok(Position {
-                    azimuth: azimuth_deg,
-                    altitude: altitude_deg,
-                })
-            }
-            Err(_) => Err(CoreError::StarPositionDate),
-        }
+        )
+    }
+
+    /// Effective temperature in kelvin estimated from the B-V color (Ballesteros 2012)
+    /// => Treats the star as a black body, good to a few percent for main sequence stars
+    pub fn temperature(&self) -> Option<f64> {
+        self.b_v_color.map(|b_v_color| {
+            4600.0 * (1.0 / (0.92 * b_v_color + 1.7) + 1.0 / (0.92 * b_v_color + 0.62))
+        })
     }
 }
@@ -1,11 +1,20 @@
 use crate::celestial_body::position_from_ecliptic_coords;
-use crate::position::observer::Observer;
-use crate::position::time::Time;
+use crate::position::astrometry::Astrometry;
 use crate::position::Position;
+use astro::coords::EclPoint;
 use tracing::instrument;
 
+/// Constant of annual aberration in radians (20.4898")
+const ABERRATION: f64 = 20.4898 / 3600.0 * std::f64::consts::PI / 180.0;
+
 #[instrument(skip_all, name = "skyseeker::sun::position")]
-pub fn sun_position(observer: &Observer, time: &Time) -> Position {
-    let (ecliptic_coords, _) = astro::sun::geocent_ecl_pos(time.get_astro_julian_day());
-    position_from_ecliptic_coords(ecliptic_coords, observer, time)
+pub fn sun_position(astrometry: &Astrometry) -> Position {
+    let (ecliptic_coords, earth_sun_distance) =
+        astro::sun::geocent_ecl_pos(astrometry.julian_ephemeris_day());
+    // astro::sun returns the geometric longitude, the apparent one lags behind by the aberration
+    let apparent_coords = EclPoint {
+        long: ecliptic_coords.long - ABERRATION / earth_sun_distance,
+        lat: ecliptic_coords.lat,
+    };
+    position_from_ecliptic_coords(apparent_coords, earth_sun_distance, astrometry)
 }
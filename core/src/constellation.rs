@@ -0,0 +1,373 @@
+use bincode::{Decode, Encode};
+use sofars::pnp::{fw2m, pfw06};
+use sofars::vm::{anp, c2s, rxp, s2c, trxp};
+use std::sync::LazyLock;
+
+/// Epoch B1875.0 as a TT Julian day, the IAU boundaries follow its parallels and hour circles
+const B1875: f64 = 2405889.258550475;
+
+/// Longest boundary segment in degrees
+/// => Precession bends the B1875.0 parallels and hour circles, short segments follow the curve
+const BOUNDARY_SEGMENT_LENGTH: f64 = 1.0;
+/// Distance either side of a boundary to tell which constellations it separates, in degrees
+const BOUNDARY_PROBE: f64 = 1e-6;
+
+/// Rotation from ICRS to the mean equator and equinox of B1875.0
+static ICRS_TO_B1875: LazyLock<[[f64; 3]; 3]> = LazyLock::new(|| {
+    let (gamma_bar, phi_bar, psi_bar, epsilon) = pfw06(B1875, 0.0);
+    let mut rotation = [[0.0; 3]; 3];
+    fw2m(gamma_bar, phi_bar, psi_bar, epsilon, &mut rotation);
+    rotation
+});
+
+/// One of the 88 IAU constellations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Constellation {
+    /// Three letter IAU abbreviation, e.g. "Boo"
+    pub abbreviation: &'static str,
+    pub name: &'static str,
+    /// Genitive form used in star designations, e.g. "Bootis" in "Alpha Bootis"
+    pub genitive: &'static str,
+}
+
+impl Constellation {
+    /// Looks a constellation up by its abbreviation, case-insensitive
+    pub fn from_abbreviation(abbreviation: &str) -> Option<&'static Constellation> {
+        CONSTELLATIONS.iter().find(|constellation| {
+            constellation
+                .abbreviation
+                .eq_ignore_ascii_case(abbreviation)
+        })
+    }
+}
+
+/// A band of sky belonging to one constellation, from the IAU boundaries of Delporte (1930)
+/// => Referred to the mean equator and equinox of B1875.0, angles in degrees
+/// => Bounded by two hour circles and the parallel below, the band above cuts off its top
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct BoundaryZone {
+    pub right_ascension_low: f64,
+    pub right_ascension_high: f64,
+    pub declination_low: f64,
+    pub abbreviation: String,
+}
+
+/// A piece of the outline of a constellation, end points as J2000.0 (ICRS) places in degrees
+#[derive(Debug, Clone, Copy)]
+pub struct BoundarySegment {
+    /// Constellation on whose edge the segment lies, the one on the other side has its own outline without it
+    pub constellation: &'static Constellation,
+    pub start: (f64, f64),
+    pub end: (f64, f64),
+}
+
+/// Stick figure of a constellation, lines drawn between pairs of stars given by their body ids
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct ConstellationFigure {
+    pub abbreviation: String,
+    pub lines: Vec<(String, String)>,
+}
+
+impl ConstellationFigure {
+    pub fn constellation(&self) -> Option<&'static Constellation> {
+        Constellation::from_abbreviation(&self.abbreviation)
+    }
+}
+
+/// The IAU constellation boundaries, to tell which constellation any point of the sky is in
+/// => Zones are kept from north to south, the first one containing a point is its constellation (Roman 1987)
+#[derive(Debug, Default, Clone, Encode, Decode)]
+pub struct ConstellationBoundaries {
+    zones: Vec<BoundaryZone>,
+}
+
+impl ConstellationBoundaries {
+    pub fn new(mut zones: Vec<BoundaryZone>) -> Self {
+        // Stable, so zones sharing a lower declination keep the order of the table
+        zones.sort_by(|a, b| b.declination_low.total_cmp(&a.declination_low));
+        Self { zones }
+    }
+
+    pub fn zones(&self) -> &[BoundaryZone] {
+        &self.zones
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.zones.is_empty()
+    }
+
+    /// Constellation containing a J2000.0 (ICRS) place, angles in degrees
+    /// => None if no boundaries are loaded
+    pub fn find(&self, right_ascension: f64, declination: f64) -> Option<&'static Constellation> {
+        self.find_direction(&s2c(right_ascension.to_radians(), declination.to_radians()))
+    }
+
+    /// Constellation containing a place referred to the equinox of B1875.0, angles in degrees
+    pub fn find_b1875(
+        &self,
+        right_ascension: f64,
+        declination: f64,
+    ) -> Option<&'static Constellation> {
+        self.zone_at(right_ascension, declination)
+            .and_then(|index| self.zone_constellation(index))
+    }
+
+    /// Outlines of all constellations, split into segments of at most a degree
+    /// => Every boundary is drawn once, it is left out where a zone only borders its own constellation
+    /// => Edges are first cut where other zones' edges meet them, so each piece has a single neighbour
+    pub fn segments(&self) -> Vec<BoundarySegment> {
+        let mut segments = Vec::new();
+        for (index, zone) in self.zones.iter().enumerate() {
+            let Some(constellation) = self.zone_constellation(index) else {
+                continue;
+            };
+
+            // Lower edge, the zone above it is the one it belongs to
+            if zone.declination_low > -90.0 {
+                let pieces = split_at(
+                    zone.right_ascension_low,
+                    zone.right_ascension_high,
+                    self.zones
+                        .iter()
+                        .flat_map(|other| [other.right_ascension_low, other.right_ascension_high]),
+                );
+                for (low, high) in pieces {
+                    let middle = (low + high) / 2.0;
+                    let below = self.find_b1875(middle, zone.declination_low - BOUNDARY_PROBE);
+                    if self.zone_at(middle, zone.declination_low + BOUNDARY_PROBE) == Some(index)
+                        && below != Some(constellation)
+                    {
+                        segments.push(segment_b1875(
+                            constellation,
+                            (low, zone.declination_low),
+                            (high, zone.declination_low),
+                        ));
+                    }
+                }
+            }
+
+            // Sides, from the lower edge up to where zones further north take over
+            for (right_ascension, inside, is_low_side) in [
+                (zone.right_ascension_low, BOUNDARY_PROBE, true),
+                (zone.right_ascension_high, -BOUNDARY_PROBE, false),
+            ] {
+                let ceiling = self.zones[..index]
+                    .iter()
+                    .filter(|other| contains_right_ascension(other, right_ascension + inside))
+                    .map(|other| other.declination_low)
+                    .fold(90.0, f64::min);
+                let pieces = split_at(
+                    zone.declination_low,
+                    ceiling,
+                    self.zones.iter().map(|other| other.declination_low),
+                );
+                for (low, high) in pieces {
+                    let middle = (low + high) / 2.0;
+                    let Some(outside_index) = self.zone_at(right_ascension - inside, middle) else {
+                        continue;
+                    };
+                    // A side shared by two zones is drawn with the zone it is the lower side of
+                    let outside = &self.zones[outside_index];
+                    if self.zone_constellation(outside_index) == Some(constellation)
+                        || (!is_low_side
+                            && outside.right_ascension_low
+                                == zone.right_ascension_high.rem_euclid(360.0))
+                    {
+                        continue;
+                    }
+                    segments.push(segment_b1875(
+                        constellation,
+                        (right_ascension, low),
+                        (right_ascension, high),
+                    ));
+                }
+            }
+        }
+        segments
+    }
+
+    /// Index of the zone containing a B1875.0 place, angles in degrees
+    fn zone_at(&self, right_ascension: f64, declination: f64) -> Option<usize> {
+        let right_ascension = right_ascension.rem_euclid(360.0);
+        self.zones.iter().position(|zone| {
+            declination >= zone.declination_low && contains_right_ascension(zone, right_ascension)
+        })
+    }
+
+    fn zone_constellation(&self, index: usize) -> Option<&'static Constellation> {
+        Constellation::from_abbreviation(&self.zones[index].abbreviation)
+    }
+
+    /// Constellation containing an ICRS unit vector
+    pub(crate) fn find_direction(&self, direction: &[f64; 3]) -> Option<&'static Constellation> {
+        let mut b1875 = [0.0; 3];
+        rxp(&ICRS_TO_B1875, direction, &mut b1875);
+        let (right_ascension, declination) = c2s(&b1875);
+        self.find_b1875(anp(right_ascension).to_degrees(), declination.to_degrees())
+    }
+}
+
+fn contains_right_ascension(zone: &BoundaryZone, right_ascension: f64) -> bool {
+    let right_ascension = right_ascension.rem_euclid(360.0);
+    right_ascension >= zone.right_ascension_low && right_ascension < zone.right_ascension_high
+}
+
+/// Cuts a range at the points inside it, then splits each part with `split`
+fn split_at(low: f64, high: f64, points: impl Iterator<Item = f64>) -> Vec<(f64, f64)> {
+    let mut cuts: Vec<f64> = points
+        .filter(|point| *point > low && *point < high)
+        .collect();
+    cuts.sort_by(f64::total_cmp);
+    cuts.dedup();
+
+    let mut bounds = vec![low];
+    bounds.extend(cuts);
+    bounds.push(high);
+    bounds
+        .windows(2)
+        .flat_map(|part| split(part[0], part[1]))
+        .collect()
+}
+
+/// Splits a range into equal pieces no longer than `BOUNDARY_SEGMENT_LENGTH`
+fn split(low: f64, high: f64) -> Vec<(f64, f64)> {
+    if high <= low {
+        return Vec::new();
+    }
+    let count = ((high - low) / BOUNDARY_SEGMENT_LENGTH).ceil().max(1.0) as usize;
+    let length = (high - low) / count as f64;
+    (0..count)
+        .map(|i| {
+            let start = low + i as f64 * length;
+            let end = if i + 1 == count { high } else { start + length };
+            (start, end)
+        })
+        .collect()
+}
+
+fn segment_b1875(
+    constellation: &'static Constellation,
+    start: (f64, f64),
+    end: (f64, f64),
+) -> BoundarySegment {
+    BoundarySegment {
+        constellation,
+        start: b1875_to_icrs(start),
+        end: b1875_to_icrs(end),
+    }
+}
+
+/// J2000.0 (ICRS) place of a B1875.0 one, angles in degrees
+fn b1875_to_icrs((right_ascension, declination): (f64, f64)) -> (f64, f64) {
+    let mut icrs = [0.0; 3];
+    trxp(
+        &ICRS_TO_B1875,
+        &s2c(right_ascension.to_radians(), declination.to_radians()),
+        &mut icrs,
+    );
+    let (right_ascension, declination) = c2s(&icrs);
+    (anp(right_ascension).to_degrees(), declination.to_degrees())
+}
+
+const fn constellation(
+    abbreviation: &'static str,
+    name: &'static str,
+    genitive: &'static str,
+) -> Constellation {
+    Constellation {
+        abbreviation,
+        name,
+        genitive,
+    }
+}
+
+pub const CONSTELLATIONS: [Constellation; 88] = [
+    constellation("And", "Andromeda", "Andromedae"),
+    constellation("Ant", "Antlia", "Antliae"),
+    constellation("Aps", "Apus", "Apodis"),
+    constellation("Aqr", "Aquarius", "Aquarii"),
+    constellation("Aql", "Aquila", "Aquilae"),
+    constellation("Ara", "Ara", "Arae"),
+    constellation("Ari", "Aries", "Arietis"),
+    constellation("Aur", "Auriga", "Aurigae"),
+    constellation("Boo", "Boötes", "Boötis"),
+    constellation("Cae", "Caelum", "Caeli"),
+    constellation("Cam", "Camelopardalis", "Camelopardalis"),
+    constellation("Cnc", "Cancer", "Cancri"),
+    constellation("CVn", "Canes Venatici", "Canum Venaticorum"),
+    constellation("CMa", "Canis Major", "Canis Majoris"),
+    constellation("CMi", "Canis Minor", "Canis Minoris"),
+    constellation("Cap", "Capricornus", "Capricorni"),
+    constellation("Car", "Carina", "Carinae"),
+    constellation("Cas", "Cassiopeia", "Cassiopeiae"),
+    constellation("Cen", "Centaurus", "Centauri"),
+    constellation("Cep", "Cepheus", "Cephei"),
+    constellation("Cet", "Cetus", "Ceti"),
+    constellation("Cha", "Chamaeleon", "Chamaeleontis"),
+    constellation("Cir", "Circinus", "Circini"),
+    constellation("Col", "Columba", "Columbae"),
+    constellation("Com", "Coma Berenices", "Comae Berenices"),
+    constellation("CrA", "Corona Australis", "Coronae Australis"),
+    constellation("CrB", "Corona Borealis", "Coronae Borealis"),
+    constellation("Crv", "Corvus", "Corvi"),
+    constellation("Crt", "Crater", "Crateris"),
+    constellation("Cru", "Crux", "Crucis"),
+    constellation("Cyg", "Cygnus", "Cygni"),
+    constellation("Del", "Delphinus", "Delphini"),
+    constellation("Dor", "Dorado", "Doradus"),
+    constellation("Dra", "Draco", "Draconis"),
+    constellation("Equ", "Equuleus", "Equulei"),
+    constellation("Eri", "Eridanus", "Eridani"),
+    constellation("For", "Fornax", "Fornacis"),
+    constellation("Gem", "Gemini", "Geminorum"),
+    constellation("Gru", "Grus", "Gruis"),
+    constellation("Her", "Hercules", "Herculis"),
+    constellation("Hor", "Horologium", "Horologii"),
+    constellation("Hya", "Hydra", "Hydrae"),
+    constellation("Hyi", "Hydrus", "Hydri"),
+    constellation("Ind", "Indus", "Indi"),
+    constellation("Lac", "Lacerta", "Lacertae"),
+    constellation("Leo", "Leo", "Leonis"),
+    constellation("LMi", "Leo Minor", "Leonis Minoris"),
+    constellation("Lep", "Lepus", "Leporis"),
+    constellation("Lib", "Libra", "Librae"),
+    constellation("Lup", "Lupus", "Lupi"),
+    constellation("Lyn", "Lynx", "Lyncis"),
+    constellation("Lyr", "Lyra", "Lyrae"),
+    constellation("Men", "Mensa", "Mensae"),
+    constellation("Mic", "Microscopium", "Microscopii"),
+    constellation("Mon", "Monoceros", "Monocerotis"),
+    constellation("Mus", "Musca", "Muscae"),
+    constellation("Nor", "Norma", "Normae"),
+    constellation("Oct", "Octans", "Octantis"),
+    constellation("Oph", "Ophiuchus", "Ophiuchi"),
+    constellation("Ori", "Orion", "Orionis"),
+    constellation("Pav", "Pavo", "Pavonis"),
+    constellation("Peg", "Pegasus", "Pegasi"),
+    constellation("Per", "Perseus", "Persei"),
+    constellation("Phe", "Phoenix", "Phoenicis"),
+    constellation("Pic", "Pictor", "Pictoris"),
+    constellation("Psc", "Pisces", "Piscium"),
+    constellation("PsA", "Piscis Austrinus", "Piscis Austrini"),
+    constellation("Pup", "Puppis", "Puppis"),
+    constellation("Pyx", "Pyxis", "Pyxidis"),
+    constellation("Ret", "Reticulum", "Reticuli"),
+    constellation("Sge", "Sagitta", "Sagittae"),
+    constellation("Sgr", "Sagittarius", "Sagittarii"),
+    constellation("Sco", "Scorpius", "Scorpii"),
+    constellation("Scl", "Sculptor", "Sculptoris"),
+    constellation("Sct", "Scutum", "Scuti"),
+    constellation("Ser", "Serpens", "Serpentis"),
+    constellation("Sex", "Sextans", "Sextantis"),
+    constellation("Tau", "Taurus", "Tauri"),
+    constellation("Tel", "Telescopium", "Telescopii"),
+    constellation("Tri", "Triangulum", "Trianguli"),
+    constellation("TrA", "Triangulum Australe", "Trianguli Australis"),
+    constellation("Tuc", "Tucana", "Tucanae"),
+    constellation("UMa", "Ursa Major", "Ursae Majoris"),
+    constellation("UMi", "Ursa Minor", "Ursae Minoris"),
+    constellation("Vel", "Vela", "Velorum"),
+    constellation("Vir", "Virgo", "Virginis"),
+    constellation("Vol", "Volans", "Volantis"),
+    constellation("Vul", "Vulpecula", "Vulpeculae"),
+];
@@ -16,10 +16,24 @@ pub enum CoreError {
     AngleFormatToRadiansBadSeconds,
     #[error("Unable to convert angle format to radians")]
     AngleFormatToRadians,
+    #[error("Invalid observer location")]
+    ObserverLocation,
+    #[error("Invalid two-line element set: {0}")]
+    SatelliteElements(String),
+    #[error("Unable to propagate satellite orbit: {0}")]
+    SatellitePropagation(String),
+    #[error("Invalid search polygon: needs at least 3 vertices, got {0}")]
+    SearchPolygon(usize),
     #[error("Body not found: id = '{0}'")]
     BodyNotFound(String),
-    #[error("Unable to calculate star position: invalid date")]
-    StarPositionDate,
+    #[error("Body is not a satellite: id = '{0}'")]
+    NotASatellite(String),
+    #[error("Rise and set are not searched for satellites, use satellite_passes: id = '{0}'")]
+    SatelliteRiseSet(String),
+    #[error("Unable to set up astrometry parameters: invalid date")]
+    AstrometryDate,
+    #[error("Date outside the range of the Earth ephemeris, years 1900 to 2100")]
+    EarthEphemerisDate,
     #[error("Invalid time: bad year")]
     TimeBadYear,
     #[error("Invalid time: bad month")]
@@ -34,6 +48,8 @@ pub enum CoreError {
     TimeBadSecond,
     #[error("Invalid time: unknown")]
     TimeBadUnknown,
+    #[error("Invalid time: unable to convert from Julian day")]
+    TimeFromJulianDay,
     #[error("Unable to convert time format to radians: bad hours")]
     TimeFormatToRadiansBadHours,
     #[error("Unable to convert time format to radians: bad minutes")]
@@ -0,0 +1,152 @@
+use crate::error::CoreResult;
+
+pub mod conjunction;
+pub mod eclipse;
+pub mod occultation;
+pub mod rise_set;
+pub mod satellite_pass;
+pub mod twilight;
+
+/// Precision event times are refined to, in days
+/// => 1 second
+const TIME_PRECISION: f64 = 1.0 / 86400.0;
+
+/// The moment a sampled quantity crosses a threshold
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Crossing {
+    /// UTC quasi Julian Date of the crossing
+    pub julian_day: f64,
+    /// Whether the quantity goes from below to above the threshold
+    pub rising: bool,
+}
+
+/// A quantity sampled at fixed steps over a time window
+/// => The step has to be small enough to not skip over two crossings at once
+pub(crate) struct Scan {
+    samples: Vec<(f64, f64)>,
+}
+
+impl Scan {
+    pub fn new(
+        start: f64,
+        end: f64,
+        step: f64,
+        f: &mut impl FnMut(f64) -> CoreResult<f64>,
+    ) -> CoreResult<Self> {
+        let count = ((end - start) / step).ceil().max(1.0) as usize;
+        let mut samples = Vec::with_capacity(count + 1);
+        for i in 0..=count {
+            let julian_day = (start + i as f64 * step).min(end);
+            samples.push((julian_day, f(julian_day)?));
+        }
+        Ok(Self { samples })
+    }
+
+    pub fn all_above(&self, threshold: f64) -> bool {
+        self.samples.iter().all(|(_, value)| *value > threshold)
+    }
+
+    pub fn all_below(&self, threshold: f64) -> bool {
+        self.samples.iter().all(|(_, value)| *value <= threshold)
+    }
+
+    /// All threshold crossings in chronological order, refined by bisection
+    pub fn crossings(
+        &self,
+        threshold: f64,
+        f: &mut impl FnMut(f64) -> CoreResult<f64>,
+    ) -> CoreResult<Vec<Crossing>> {
+        let mut crossings = Vec::new();
+        for pair in self.samples.windows(2) {
+            let (mut low, low_value) = pair[0];
+            let (mut high, high_value) = pair[1];
+            let rising = low_value <= threshold && high_value > threshold;
+            let falling = low_value > threshold && high_value <= threshold;
+            if !rising && !falling {
+                continue;
+            }
+
+            while high - low > TIME_PRECISION {
+                let middle = (low + high) / 2.0;
+                let above = f(middle)? > threshold;
+                if above == rising {
+                    high = middle;
+                } else {
+                    low = middle;
+                }
+            }
+
+            crossings.push(Crossing {
+                julian_day: (low + high) / 2.0,
+                rising,
+            });
+        }
+        Ok(crossings)
+    }
+
+    /// The highest local maximum strictly inside the window, refined by golden-section search
+    /// => Returns (julian_day, value)
+    pub fn maximum(
+        &self,
+        f: &mut impl FnMut(f64) -> CoreResult<f64>,
+    ) -> CoreResult<Option<(f64, f64)>> {
+        let best = self
+            .samples
+            .windows(3)
+            .filter(|w| w[0].1 < w[1].1 && w[1].1 >= w[2].1)
+            .max_by(|a, b| a[1].1.total_cmp(&b[1].1));
+
+        let Some(window) = best else {
+            return Ok(None);
+        };
+
+        let mut negated = |julian_day: f64| -> CoreResult<f64> { Ok(-f(julian_day)?) };
+        let (julian_day, value) = refine_minimum(window[0].0, window[2].0, &mut negated)?;
+        Ok(Some((julian_day, -value)))
+    }
+
+    /// All local minima strictly inside the window in chronological order, refined by golden-section search
+    /// => Returns (julian_day, value) pairs
+    pub fn minima(
+        &self,
+        f: &mut impl FnMut(f64) -> CoreResult<f64>,
+    ) -> CoreResult<Vec<(f64, f64)>> {
+        self.samples
+            .windows(3)
+            .filter(|w| w[0].1 > w[1].1 && w[1].1 <= w[2].1)
+            .map(|window| refine_minimum(window[0].0, window[2].0, f))
+            .collect()
+    }
+}
+
+/// Golden-section search for the minimum of a unimodal function between `start` and `end`
+fn refine_minimum(
+    start: f64,
+    end: f64,
+    f: &mut impl FnMut(f64) -> CoreResult<f64>,
+) -> CoreResult<(f64, f64)> {
+    let inverse_phi = (5f64.sqrt() - 1.0) / 2.0;
+    let (mut a, mut b) = (start, end);
+    let mut c = b - (b - a) * inverse_phi;
+    let mut d = a + (b - a) * inverse_phi;
+    let mut f_c = f(c)?;
+    let mut f_d = f(d)?;
+    while b - a > TIME_PRECISION {
+        if f_c < f_d {
+            b = d;
+            d = c;
+            f_d = f_c;
+            c = b - (b - a) * inverse_phi;
+            f_c = f(c)?;
+        } else {
+            a = c;
+            c = d;
+            f_c = f_d;
+            d = a + (b - a) * inverse_phi;
+            f_d = f(d)?;
+        }
+    }
+
+    let julian_day = (a + b) / 2.0;
+    Ok((julian_day, f(julian_day)?))
+}
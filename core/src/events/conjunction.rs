@@ -0,0 +1,94 @@
+use crate::celestial_body::CelestialBody;
+use crate::error::CoreResult;
+use crate::events::Scan;
+use crate::position::astrometry::Astrometry;
+use crate::position::earth_orientation::EarthOrientation;
+use crate::position::observer::Observer;
+use crate::position::time::Time;
+use crate::position::Separation;
+use tracing::instrument;
+
+/// Step between separation samples in days when neither body moves fast
+/// => 6 hours, the planets change their separation by at most a few degrees per day
+const SCAN_STEP: f64 = 0.25;
+/// Step when the Moon is involved
+/// => 1 hour, the Moon moves about its own diameter in that time
+const MOON_SCAN_STEP: f64 = 1.0 / 24.0;
+/// Step when a satellite is involved
+/// => 10 seconds, satellites in low Earth orbit cross the sky in minutes
+const SATELLITE_SCAN_STEP: f64 = 10.0 / 86400.0;
+
+/// Closest approach of two bodies on the sky
+#[derive(Debug, Clone, Copy)]
+pub struct Conjunction {
+    pub time: Time,
+    /// Separation and position angle of the second body from the first at closest approach
+    pub separation: Separation,
+}
+
+/// Separation and position angle of `b` as seen from `a`
+pub fn separation(
+    a: &CelestialBody,
+    b: &CelestialBody,
+    observer: &Observer,
+    time: &Time,
+    earth_orientation: &EarthOrientation,
+) -> CoreResult<Separation> {
+    let astrometry = Astrometry::new(observer, time, earth_orientation)?;
+    separation_with(a, b, &astrometry)
+}
+
+fn separation_with(
+    a: &CelestialBody,
+    b: &CelestialBody,
+    astrometry: &Astrometry,
+) -> CoreResult<Separation> {
+    let position_a = a.position_with(astrometry)?;
+    let position_b = b.position_with(astrometry)?;
+    Ok(position_a.separation(&position_b))
+}
+
+/// Searches between `start` and `end` for the moments two bodies come closest on the sky
+/// => Only approaches closer than `max_separation` (in degrees) are returned, in chronological order
+#[instrument(skip_all, name = "skyseeker::conjunctions")]
+pub fn conjunctions(
+    a: &CelestialBody,
+    b: &CelestialBody,
+    observer: &Observer,
+    start: &Time,
+    end: &Time,
+    max_separation: f64,
+    earth_orientation: &EarthOrientation,
+) -> CoreResult<Vec<Conjunction>> {
+    let mut separation_at = |julian_day: f64| -> CoreResult<f64> {
+        let time = Time::from_julian_day(julian_day)?;
+        let astrometry = Astrometry::new(observer, &time, earth_orientation)?;
+        Ok(separation_with(a, b, &astrometry)?.separation)
+    };
+
+    let step = if a.is_satellite() || b.is_satellite() {
+        SATELLITE_SCAN_STEP
+    } else if a.is_moon() || b.is_moon() {
+        MOON_SCAN_STEP
+    } else {
+        SCAN_STEP
+    };
+    let scan = Scan::new(
+        start.get_julian_day()?,
+        end.get_julian_day()?,
+        step,
+        &mut separation_at,
+    )?;
+
+    scan.minima(&mut separation_at)?
+        .into_iter()
+        .filter(|(_, separation)| *separation < max_separation)
+        .map(|(julian_day, _)| {
+            let time = Time::from_julian_day(julian_day)?;
+            Ok(Conjunction {
+                time,
+                separation: separation(a, b, observer, &time, earth_orientation)?,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,489 @@
+use crate::celestial_body::moon::{moon_position, next_phase_time};
+use crate::celestial_body::sun::sun_position;
+use crate::error::CoreResult;
+use crate::events::{Crossing, Scan};
+use crate::position::astrometry::Astrometry;
+use crate::position::earth_orientation::EarthOrientation;
+use crate::position::observer::Observer;
+use crate::position::time::Time;
+use crate::position::{Distance, Position};
+use astro::lunar::Phase;
+use tracing::instrument;
+
+/// Astronomical unit in km
+const ASTRONOMICAL_UNIT: f64 = 149597870.7;
+/// Radius of the Sun in km (IAU 2015 nominal value)
+const SUN_RADIUS: f64 = 695700.0;
+/// Mean radius of the Moon in km
+const MOON_RADIUS: f64 = 1737.4;
+/// Equatorial radius of the Earth in km
+const EARTH_RADIUS: f64 = 6378.137;
+/// Enlargement of the Earth's shadow by the atmosphere (Danjon)
+/// => The Earth's radius is increased by 1/85
+const SHADOW_ENLARGEMENT: f64 = 1.0 + 1.0 / 85.0;
+/// Largest ecliptic latitude of the Moon at syzygy that still allows an eclipse in degrees
+/// => ~1.58° for both partial solar and penumbral lunar eclipses, with some margin
+const ECLIPSE_LATITUDE_LIMIT: f64 = 1.7;
+/// Half the window searched around each new or full moon in days
+/// => 5 hours, longer than half of the longest eclipses
+const SEARCH_WINDOW: f64 = 5.0 / 24.0;
+/// Step between Sun-Moon separation samples in days
+/// => 2 minutes, short enough to not skip over the central phase of a solar eclipse
+const SOLAR_SCAN_STEP: f64 = 2.0 / 1440.0;
+/// Step between Moon-shadow separation samples in days
+/// => 5 minutes, short enough to not skip over the total phase of a lunar eclipse
+const LUNAR_SCAN_STEP: f64 = 5.0 / 1440.0;
+/// Step between altitude samples when checking whether an eclipse is visible
+const VISIBILITY_SCAN_STEP: f64 = 5.0 / 1440.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EclipseKind {
+    Solar,
+    Lunar,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EclipseType {
+    /// Solar: the Moon covers part of the Sun
+    Partial,
+    /// Solar: the Moon passes inside the Sun's disk, leaving a ring
+    Annular,
+    /// Solar: the Moon covers the whole Sun
+    /// => Lunar: the Moon is entirely inside the umbra
+    Total,
+    /// Lunar: the Moon only enters the penumbra
+    Penumbral,
+    /// Lunar: part of the Moon enters the umbra
+    Umbral,
+}
+
+impl EclipseType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Partial => "Partial",
+            Self::Annular => "Annular",
+            Self::Total => "Total",
+            Self::Penumbral => "Penumbral",
+            Self::Umbral => "Partial umbral",
+        }
+    }
+}
+
+/// An eclipse as seen by the observer
+/// => Solar eclipses are computed for the observer's location, lunar eclipses are the same for everyone who sees the Moon
+#[derive(Debug, Clone, Copy)]
+pub struct Eclipse {
+    pub kind: EclipseKind,
+    pub eclipse_type: EclipseType,
+    /// The Moon enters the penumbra (P1)
+    /// => Lunar eclipses only
+    pub penumbral_begin: Option<Time>,
+    /// First contact (C1) or the Moon enters the umbra (U1)
+    /// => None for penumbral lunar eclipses
+    pub partial_begin: Option<Time>,
+    /// Second contact (C2) or the Moon is entirely inside the umbra (U2)
+    /// => None without an annular or total phase
+    pub central_begin: Option<Time>,
+    pub maximum: Time,
+    /// Third contact (C3) or the Moon starts leaving the umbra (U3)
+    pub central_end: Option<Time>,
+    /// Fourth contact (C4) or the Moon leaves the umbra (U4)
+    pub partial_end: Option<Time>,
+    /// The Moon leaves the penumbra (P4)
+    pub penumbral_end: Option<Time>,
+    /// Fraction of the Sun's diameter covered by the Moon, or of the Moon's diameter inside the shadow, at maximum
+    /// => Umbral magnitude for umbral and total lunar eclipses, penumbral magnitude for penumbral ones
+    pub magnitude: f64,
+    /// Fraction of the Sun's disk area covered by the Moon, or of the Moon's disk area inside the shadow, at maximum
+    pub obscuration: f64,
+    /// Altitude of the Sun at maximum in degrees
+    pub sun_altitude: f64,
+    /// Altitude of the Moon at maximum in degrees
+    pub moon_altitude: f64,
+}
+
+/// Searches for solar and lunar eclipses between `start` and `end` that the observer can see
+/// => An eclipse counts as visible if the Sun (solar) or Moon (lunar) is above the horizon for part of it
+/// => Eclipses are returned in chronological order
+#[instrument(skip_all, name = "skyseeker::eclipses")]
+pub fn eclipses(
+    observer: &Observer,
+    start: &Time,
+    end: &Time,
+    earth_orientation: &EarthOrientation,
+) -> CoreResult<Vec<Eclipse>> {
+    let start_julian_day = start.get_julian_day()?;
+    let end_julian_day = end.get_julian_day()?;
+    let mut eclipses = Vec::new();
+
+    for (phase, kind) in [
+        (Phase::New, EclipseKind::Solar),
+        (Phase::Full, EclipseKind::Lunar),
+    ] {
+        let mut time = start.add_days(-SEARCH_WINDOW)?;
+        loop {
+            let syzygy = next_phase_time(&time, &phase)?;
+            let syzygy_julian_day = syzygy.get_julian_day()?;
+            if syzygy_julian_day - SEARCH_WINDOW > end_julian_day {
+                break;
+            }
+            time = syzygy.add_days(1.0)?;
+
+            if !is_eclipse_season(&syzygy)? {
+                continue;
+            }
+            let eclipse = match kind {
+                EclipseKind::Solar => {
+                    solar_eclipse(syzygy_julian_day, observer, earth_orientation)?
+                }
+                EclipseKind::Lunar => {
+                    lunar_eclipse(syzygy_julian_day, observer, earth_orientation)?
+                }
+            };
+            if let Some(eclipse) = eclipse {
+                let maximum = eclipse.maximum.get_julian_day()?;
+                if maximum >= start_julian_day && maximum <= end_julian_day {
+                    eclipses.push(eclipse);
+                }
+            }
+        }
+    }
+
+    eclipses.sort_by(|a, b| {
+        a.maximum
+            .get_astro_julian_day()
+            .total_cmp(&b.maximum.get_astro_julian_day())
+    });
+    Ok(eclipses)
+}
+
+/// Whether the Moon is close enough to the ecliptic at a new or full moon for an eclipse
+fn is_eclipse_season(syzygy: &Time) -> CoreResult<bool> {
+    let (moon_ecliptic, _) = astro::lunar::geocent_ecl_pos(syzygy.get_julian_ephemeris_day()?);
+    Ok(moon_ecliptic.lat.to_degrees().abs() < ECLIPSE_LATITUDE_LIMIT)
+}
+
+/// The local circumstances of a solar eclipse around a new moon, None if the Moon misses the Sun for the observer
+fn solar_eclipse(
+    new_moon: f64,
+    observer: &Observer,
+    earth_orientation: &EarthOrientation,
+) -> CoreResult<Option<Eclipse>> {
+    let disks_at = |julian_day: f64| -> CoreResult<SolarDisks> {
+        let time = Time::from_julian_day(julian_day)?;
+        let astrometry = Astrometry::new(observer, &time, earth_orientation)?;
+        let sun = sun_position(&astrometry);
+        let moon = moon_position(&astrometry);
+        Ok(SolarDisks {
+            separation: sun.separation(&moon).separation,
+            sun_radius: semidiameter(SUN_RADIUS, &sun),
+            moon_radius: semidiameter(MOON_RADIUS, &moon),
+            sun,
+            moon,
+        })
+    };
+    // Gap between the limbs, negative while the disks overlap
+    let mut limb_gap = |julian_day: f64| -> CoreResult<f64> {
+        let disks = disks_at(julian_day)?;
+        Ok(disks.separation - disks.sun_radius - disks.moon_radius)
+    };
+
+    let scan = Scan::new(
+        new_moon - SEARCH_WINDOW,
+        new_moon + SEARCH_WINDOW,
+        SOLAR_SCAN_STEP,
+        &mut limb_gap,
+    )?;
+    if scan.all_above(0.0) {
+        return Ok(None);
+    }
+    let (first_contact, fourth_contact) = contacts(&scan.crossings(0.0, &mut limb_gap)?);
+    let Some((maximum, _)) = scan
+        .minima(&mut limb_gap)?
+        .into_iter()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+    else {
+        return Ok(None);
+    };
+
+    let disks = disks_at(maximum)?;
+    let central_gap = disks.separation - (disks.sun_radius - disks.moon_radius).abs();
+    let eclipse_type = if central_gap >= 0.0 {
+        EclipseType::Partial
+    } else if disks.moon_radius > disks.sun_radius {
+        EclipseType::Total
+    } else {
+        EclipseType::Annular
+    };
+
+    let (mut central_begin, mut central_end) = (None, None);
+    if eclipse_type != EclipseType::Partial {
+        let mut central_gap = |julian_day: f64| -> CoreResult<f64> {
+            let disks = disks_at(julian_day)?;
+            Ok(disks.separation - (disks.sun_radius - disks.moon_radius).abs())
+        };
+        let central_scan = Scan::new(
+            maximum - SEARCH_WINDOW / 10.0,
+            maximum + SEARCH_WINDOW / 10.0,
+            SOLAR_SCAN_STEP / 10.0,
+            &mut central_gap,
+        )?;
+        (central_begin, central_end) = contacts(&central_scan.crossings(0.0, &mut central_gap)?);
+    }
+
+    let mut sun_altitude_at =
+        |julian_day: f64| -> CoreResult<f64> { Ok(disks_at(julian_day)?.sun.altitude) };
+    if !is_visible(first_contact, fourth_contact, maximum, &mut sun_altitude_at)? {
+        return Ok(None);
+    }
+
+    Ok(Some(Eclipse {
+        kind: EclipseKind::Solar,
+        eclipse_type,
+        penumbral_begin: None,
+        partial_begin: to_time(first_contact)?,
+        central_begin: to_time(central_begin)?,
+        maximum: Time::from_julian_day(maximum)?,
+        central_end: to_time(central_end)?,
+        partial_end: to_time(fourth_contact)?,
+        penumbral_end: None,
+        magnitude: (disks.sun_radius + disks.moon_radius - disks.separation)
+            / (2.0 * disks.sun_radius),
+        obscuration: covered_fraction(disks.sun_radius, disks.moon_radius, disks.separation),
+        sun_altitude: disks.sun.altitude,
+        moon_altitude: disks.moon.altitude,
+    }))
+}
+
+/// The circumstances of a lunar eclipse around a full moon, None if the Moon misses the penumbra
+/// => The shadow geometry is geocentric, the observer only decides about visibility and altitudes
+fn lunar_eclipse(
+    full_moon: f64,
+    observer: &Observer,
+    earth_orientation: &EarthOrientation,
+) -> CoreResult<Option<Eclipse>> {
+    let shadow_at = |julian_day: f64| -> CoreResult<EarthShadow> {
+        let time = Time::from_julian_day(julian_day)?;
+        Ok(EarthShadow::new(time.get_julian_ephemeris_day()?))
+    };
+    let mut penumbral_gap = |julian_day: f64| -> CoreResult<f64> {
+        let shadow = shadow_at(julian_day)?;
+        Ok(shadow.separation - shadow.penumbra_radius - shadow.moon_radius)
+    };
+
+    let scan = Scan::new(
+        full_moon - SEARCH_WINDOW,
+        full_moon + SEARCH_WINDOW,
+        LUNAR_SCAN_STEP,
+        &mut penumbral_gap,
+    )?;
+    if scan.all_above(0.0) {
+        return Ok(None);
+    }
+    let (penumbral_begin, penumbral_end) = contacts(&scan.crossings(0.0, &mut penumbral_gap)?);
+    let Some((maximum, _)) = scan
+        .minima(&mut penumbral_gap)?
+        .into_iter()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+    else {
+        return Ok(None);
+    };
+
+    let shadow = shadow_at(maximum)?;
+    let umbral_magnitude =
+        (shadow.umbra_radius + shadow.moon_radius - shadow.separation) / (2.0 * shadow.moon_radius);
+    let penumbral_magnitude = (shadow.penumbra_radius + shadow.moon_radius - shadow.separation)
+        / (2.0 * shadow.moon_radius);
+    let eclipse_type = if umbral_magnitude >= 1.0 {
+        EclipseType::Total
+    } else if umbral_magnitude > 0.0 {
+        EclipseType::Umbral
+    } else {
+        EclipseType::Penumbral
+    };
+
+    let (mut partial_begin, mut partial_end) = (None, None);
+    let (mut central_begin, mut central_end) = (None, None);
+    if eclipse_type != EclipseType::Penumbral {
+        let mut umbral_gap = |julian_day: f64| -> CoreResult<f64> {
+            let shadow = shadow_at(julian_day)?;
+            Ok(shadow.separation - shadow.umbra_radius - shadow.moon_radius)
+        };
+        (partial_begin, partial_end) = contacts(&scan_crossings(full_moon, &mut umbral_gap)?);
+    }
+    if eclipse_type == EclipseType::Total {
+        let mut total_gap = |julian_day: f64| -> CoreResult<f64> {
+            let shadow = shadow_at(julian_day)?;
+            Ok(shadow.separation - shadow.umbra_radius + shadow.moon_radius)
+        };
+        (central_begin, central_end) = contacts(&scan_crossings(full_moon, &mut total_gap)?);
+    }
+
+    let time = Time::from_julian_day(maximum)?;
+    let astrometry = Astrometry::new(observer, &time, earth_orientation)?;
+    let moon = moon_position(&astrometry);
+    let sun = sun_position(&astrometry);
+
+    let mut moon_altitude_at = |julian_day: f64| -> CoreResult<f64> {
+        let time = Time::from_julian_day(julian_day)?;
+        let astrometry = Astrometry::new(observer, &time, earth_orientation)?;
+        Ok(moon_position(&astrometry).altitude)
+    };
+    if !is_visible(
+        penumbral_begin,
+        penumbral_end,
+        maximum,
+        &mut moon_altitude_at,
+    )? {
+        return Ok(None);
+    }
+
+    let (magnitude, shadow_radius) = match eclipse_type {
+        EclipseType::Penumbral => (penumbral_magnitude, shadow.penumbra_radius),
+        _ => (umbral_magnitude, shadow.umbra_radius),
+    };
+
+    Ok(Some(Eclipse {
+        kind: EclipseKind::Lunar,
+        eclipse_type,
+        penumbral_begin: to_time(penumbral_begin)?,
+        partial_begin: to_time(partial_begin)?,
+        central_begin: to_time(central_begin)?,
+        maximum: time,
+        central_end: to_time(central_end)?,
+        partial_end: to_time(partial_end)?,
+        penumbral_end: to_time(penumbral_end)?,
+        magnitude,
+        obscuration: covered_fraction(shadow.moon_radius, shadow_radius, shadow.separation),
+        sun_altitude: sun.altitude,
+        moon_altitude: moon.altitude,
+    }))
+}
+
+/// Sun and Moon as seen by the observer, radii and separation in degrees
+struct SolarDisks {
+    sun: Position,
+    moon: Position,
+    separation: f64,
+    sun_radius: f64,
+    moon_radius: f64,
+}
+
+/// The Moon and the Earth's shadow at the Moon's distance as seen from the Earth's center, all in degrees
+struct EarthShadow {
+    /// Distance of the Moon's center from the shadow's axis
+    separation: f64,
+    moon_radius: f64,
+    umbra_radius: f64,
+    penumbra_radius: f64,
+}
+
+impl EarthShadow {
+    fn new(julian_ephemeris_day: f64) -> Self {
+        let (moon_ecliptic, earth_moon_distance) =
+            astro::lunar::geocent_ecl_pos(julian_ephemeris_day);
+        let (sun_ecliptic, earth_sun_distance) = astro::sun::geocent_ecl_pos(julian_ephemeris_day);
+        let earth_sun_distance = earth_sun_distance * ASTRONOMICAL_UNIT;
+
+        // The shadow's axis points away from the Sun
+        let (shadow_long, shadow_lat) =
+            (sun_ecliptic.long + std::f64::consts::PI, -sun_ecliptic.lat);
+        let separation = (shadow_lat.sin() * moon_ecliptic.lat.sin()
+            + shadow_lat.cos()
+                * moon_ecliptic.lat.cos()
+                * (moon_ecliptic.long - shadow_long).cos())
+        .clamp(-1.0, 1.0)
+        .acos();
+
+        let moon_parallax = (EARTH_RADIUS / earth_moon_distance).asin();
+        let sun_parallax = (EARTH_RADIUS / earth_sun_distance).asin();
+        let sun_radius = (SUN_RADIUS / earth_sun_distance).asin();
+
+        Self {
+            separation: separation.to_degrees(),
+            moon_radius: (MOON_RADIUS / earth_moon_distance).asin().to_degrees(),
+            umbra_radius: (SHADOW_ENLARGEMENT * moon_parallax + sun_parallax - sun_radius)
+                .to_degrees(),
+            penumbra_radius: (SHADOW_ENLARGEMENT * moon_parallax + sun_parallax + sun_radius)
+                .to_degrees(),
+        }
+    }
+}
+
+/// Crossings of zero by a limb gap within the search window around a full moon
+fn scan_crossings(
+    full_moon: f64,
+    gap: &mut impl FnMut(f64) -> CoreResult<f64>,
+) -> CoreResult<Vec<Crossing>> {
+    let scan = Scan::new(
+        full_moon - SEARCH_WINDOW,
+        full_moon + SEARCH_WINDOW,
+        LUNAR_SCAN_STEP,
+        gap,
+    )?;
+    scan.crossings(0.0, gap)
+}
+
+/// Begin and end of a phase from the crossings of its limb gap
+/// => The gap falls below zero when the phase begins and rises above it when it ends
+fn contacts(crossings: &[Crossing]) -> (Option<f64>, Option<f64>) {
+    let begin = crossings.iter().find(|crossing| !crossing.rising);
+    let end = crossings.iter().rev().find(|crossing| crossing.rising);
+    (
+        begin.map(|crossing| crossing.julian_day),
+        end.map(|crossing| crossing.julian_day),
+    )
+}
+
+/// Whether a body is above the horizon at some point between `begin` and `end`
+fn is_visible(
+    begin: Option<f64>,
+    end: Option<f64>,
+    maximum: f64,
+    altitude_at: &mut impl FnMut(f64) -> CoreResult<f64>,
+) -> CoreResult<bool> {
+    let begin = begin.unwrap_or(maximum - SEARCH_WINDOW);
+    let end = end.unwrap_or(maximum + SEARCH_WINDOW);
+    let scan = Scan::new(begin, end, VISIBILITY_SCAN_STEP, altitude_at)?;
+    Ok(!scan.all_below(0.0))
+}
+
+/// Apparent radius of a body in degrees
+pub(crate) fn semidiameter(radius: f64, position: &Position) -> f64 {
+    match position.distance {
+        Some(Distance::AstronomicalUnits(distance)) => (radius / (distance * ASTRONOMICAL_UNIT))
+            .asin()
+            .to_degrees(),
+        _ => 0.0,
+    }
+}
+
+/// Fraction of the area of a disk covered by another disk, radii and separation in the same unit
+fn covered_fraction(radius: f64, other_radius: f64, separation: f64) -> f64 {
+    if separation >= radius + other_radius {
+        return 0.0;
+    }
+    if separation <= other_radius - radius {
+        return 1.0;
+    }
+    if separation <= radius - other_radius {
+        return (other_radius / radius).powi(2);
+    }
+
+    // Area of the lens where the two disks intersect
+    let angle = ((separation.powi(2) + radius.powi(2) - other_radius.powi(2))
+        / (2.0 * separation * radius))
+        .clamp(-1.0, 1.0)
+        .acos();
+    let other_angle = ((separation.powi(2) + other_radius.powi(2) - radius.powi(2))
+        / (2.0 * separation * other_radius))
+        .clamp(-1.0, 1.0)
+        .acos();
+    let lens = radius.powi(2) * (angle - angle.sin() * angle.cos())
+        + other_radius.powi(2) * (other_angle - other_angle.sin() * other_angle.cos());
+    lens / (std::f64::consts::PI * radius.powi(2))
+}
+
+fn to_time(julian_day: Option<f64>) -> CoreResult<Option<Time>> {
+    julian_day.map(Time::from_julian_day).transpose()
+}
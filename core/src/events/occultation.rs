@@ -0,0 +1,239 @@
+use crate::celestial_body::moon::moon_position;
+use crate::celestial_body::star::Star;
+use crate::celestial_body::sun::sun_position;
+use crate::error::CoreResult;
+use crate::events::eclipse::semidiameter;
+use crate::events::Scan;
+use crate::position::astrometry::Astrometry;
+use crate::position::earth_orientation::EarthOrientation;
+use crate::position::observer::Observer;
+use crate::position::time::Time;
+use crate::position::Position;
+use tracing::instrument;
+
+/// Mean radius of the Moon in km
+const MOON_RADIUS: f64 = 1737.4;
+/// Step between Moon position samples when looking for candidate stars in days
+/// => 1 hour, the Moon moves about its own diameter in that time
+const CANDIDATE_STEP: f64 = 1.0 / 24.0;
+/// Separation from the Moon's center below which a star becomes a candidate in degrees
+/// => Moon radius (~0.28°) plus half of the Moon's hourly motion (~0.3°), with some margin
+const CANDIDATE_SEPARATION: f64 = 0.8;
+/// Step between separation samples around a candidate in days
+/// => 1 minute, the Moon crosses the shortest chords worth timing in a few minutes
+const SCAN_STEP: f64 = 1.0 / 1440.0;
+/// Distance of the closest approach from the Moon's limb within which an event counts as a graze, in degrees
+/// => 0.5', the northern or southern limit of the occultation path lies within ~50 km of the observer
+const GRAZE_MARGIN: f64 = 0.5 / 60.0;
+
+/// A star hidden by the Moon as seen by the observer
+#[derive(Debug, Clone)]
+pub struct Occultation {
+    pub star_id: String,
+    pub visual_magnitude: f64,
+    /// The star disappears behind the Moon
+    /// => None if it is already hidden at the start of the window or it only grazes the limb without disappearing
+    pub disappearance: Option<OccultationContact>,
+    /// The star reappears from behind the Moon
+    /// => None if it is still hidden at the end of the window or it only grazes the limb without disappearing
+    pub reappearance: Option<OccultationContact>,
+    /// Moment the star is closest to the Moon's center
+    pub closest_approach: Time,
+    /// Set if the star passes close to the northern or southern limb
+    /// => The observer is near a limit of the occultation path and lunar mountains may hide and reveal it several times
+    pub graze: Option<GrazeLimit>,
+}
+
+/// The moment a star touches the Moon's limb
+#[derive(Debug, Clone, Copy)]
+pub struct OccultationContact {
+    pub time: Time,
+    pub limb: Limb,
+    /// Position angle of the contact point on the limb in degrees
+    /// => Measured from celestial north through east (0°=N, 90°=E)
+    pub position_angle: f64,
+    /// Altitude of the Moon in degrees
+    pub moon_altitude: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limb {
+    /// Lit by the Sun, events are harder to time against the glare
+    Bright,
+    Dark,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrazeLimit {
+    North,
+    South,
+}
+
+/// Searches for occultations of `stars` by the Moon between `start` and `end`
+/// => Only events with the Moon above the horizon are returned, in chronological order
+#[instrument(skip_all, name = "skyseeker::occultations")]
+pub fn occultations<'a>(
+    stars: impl IntoIterator<Item = &'a Star>,
+    observer: &Observer,
+    start: &Time,
+    end: &Time,
+    earth_orientation: &EarthOrientation,
+) -> CoreResult<Vec<Occultation>> {
+    let start_julian_day = start.get_julian_day()?;
+    let end_julian_day = end.get_julian_day()?;
+
+    // Stars move so little during the window that one apparent place is enough to find candidates
+    let middle = Time::from_julian_day((start_julian_day + end_julian_day) / 2.0)?;
+    let middle_astrometry = Astrometry::new(observer, &middle, earth_orientation)?;
+    let stars: Vec<(&Star, Position)> = stars
+        .into_iter()
+        .map(|star| (star, star.position_with(&middle_astrometry)))
+        .collect();
+
+    let count = ((end_julian_day - start_julian_day) / CANDIDATE_STEP).ceil() as usize;
+    let mut moon_samples = Vec::with_capacity(count + 1);
+    for i in 0..=count {
+        let julian_day = (start_julian_day + i as f64 * CANDIDATE_STEP).min(end_julian_day);
+        let time = Time::from_julian_day(julian_day)?;
+        let astrometry = Astrometry::new(observer, &time, earth_orientation)?;
+        moon_samples.push((julian_day, moon_position(&astrometry)));
+    }
+
+    let mut occultations = Vec::new();
+    for (star, star_position) in stars {
+        // Runs of consecutive samples with the Moon close to the star, each one a separate approach
+        let mut run: Option<(f64, f64)> = None;
+        for (julian_day, moon) in &moon_samples {
+            if moon.separation(&star_position).separation < CANDIDATE_SEPARATION {
+                run = Some(run.map_or((*julian_day, *julian_day), |(first, _)| {
+                    (first, *julian_day)
+                }));
+                continue;
+            }
+            if let Some((first, last)) = run.take() {
+                occultations.extend(occultation(
+                    star,
+                    (first - CANDIDATE_STEP).max(start_julian_day),
+                    (last + CANDIDATE_STEP).min(end_julian_day),
+                    observer,
+                    earth_orientation,
+                )?);
+            }
+        }
+        if let Some((first, last)) = run {
+            occultations.extend(occultation(
+                star,
+                (first - CANDIDATE_STEP).max(start_julian_day),
+                last,
+                observer,
+                earth_orientation,
+            )?);
+        }
+    }
+
+    occultations.sort_by(|a, b| {
+        a.closest_approach
+            .get_astro_julian_day()
+            .total_cmp(&b.closest_approach.get_astro_julian_day())
+    });
+    Ok(occultations)
+}
+
+/// The occultation of one star within a window around a close approach, None if the Moon misses it
+fn occultation(
+    star: &Star,
+    start: f64,
+    end: f64,
+    observer: &Observer,
+    earth_orientation: &EarthOrientation,
+) -> CoreResult<Option<Occultation>> {
+    let contact_at = |julian_day: f64| -> CoreResult<(Position, Position, Position)> {
+        let time = Time::from_julian_day(julian_day)?;
+        let astrometry = Astrometry::new(observer, &time, earth_orientation)?;
+        Ok((
+            moon_position(&astrometry),
+            star.position_with(&astrometry),
+            sun_position(&astrometry),
+        ))
+    };
+    // Distance of the star from the Moon's limb, negative while hidden
+    let mut limb_distance = |julian_day: f64| -> CoreResult<f64> {
+        let (moon, star, _) = contact_at(julian_day)?;
+        Ok(moon.separation(&star).separation - semidiameter(MOON_RADIUS, &moon))
+    };
+
+    let scan = Scan::new(start, end, SCAN_STEP, &mut limb_distance)?;
+    if scan.all_above(GRAZE_MARGIN) {
+        return Ok(None);
+    }
+    let Some((closest_approach, closest_distance)) = scan
+        .minima(&mut limb_distance)?
+        .into_iter()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+    else {
+        return Ok(None);
+    };
+    if closest_distance > GRAZE_MARGIN {
+        return Ok(None);
+    }
+
+    let (moon, star_position, _) = contact_at(closest_approach)?;
+    let graze = (closest_distance > -GRAZE_MARGIN).then(|| {
+        let position_angle = moon.separation(&star_position).position_angle;
+        if position_angle.to_radians().cos() > 0.0 {
+            GrazeLimit::North
+        } else {
+            GrazeLimit::South
+        }
+    });
+
+    let contact = |julian_day: f64| -> CoreResult<OccultationContact> {
+        let (moon, star, sun) = contact_at(julian_day)?;
+        let position_angle = moon.separation(&star).position_angle;
+        // The lit half of the limb is centered on the direction of the Sun
+        let bright_limb_angle = moon.separation(&sun).position_angle;
+        let offset = (position_angle - bright_limb_angle + 180.0).rem_euclid(360.0) - 180.0;
+        Ok(OccultationContact {
+            time: Time::from_julian_day(julian_day)?,
+            limb: if offset.abs() < 90.0 {
+                Limb::Bright
+            } else {
+                Limb::Dark
+            },
+            position_angle,
+            moon_altitude: moon.altitude,
+        })
+    };
+
+    let crossings = scan.crossings(0.0, &mut limb_distance)?;
+    let disappearance = crossings
+        .iter()
+        .find(|crossing| !crossing.rising)
+        .map(|crossing| contact(crossing.julian_day))
+        .transpose()?;
+    let reappearance = crossings
+        .iter()
+        .rev()
+        .find(|crossing| crossing.rising)
+        .map(|crossing| contact(crossing.julian_day))
+        .transpose()?;
+
+    let visible = [&disappearance, &reappearance]
+        .into_iter()
+        .flatten()
+        .map(|contact| contact.moon_altitude)
+        .chain(std::iter::once(moon.altitude))
+        .any(|altitude| altitude > 0.0);
+    if !visible {
+        return Ok(None);
+    }
+
+    Ok(Some(Occultation {
+        star_id: star.id.clone(),
+        visual_magnitude: star.visual_magnitude,
+        disappearance,
+        reappearance,
+        closest_approach: Time::from_julian_day(closest_approach)?,
+        graze,
+    }))
+}
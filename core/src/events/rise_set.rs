@@ -0,0 +1,128 @@
+use crate::celestial_body::CelestialBody;
+use crate::error::{CoreError, CoreResult};
+use crate::events::Scan;
+use crate::position::earth_orientation::EarthOrientation;
+use crate::position::observer::Observer;
+use crate::position::time::Time;
+use crate::position::{Distance, Position};
+use tracing::instrument;
+
+/// Step between altitude samples in days
+/// => 10 minutes, fine enough to not miss the Moon briefly clearing the horizon
+const SCAN_STEP: f64 = 10.0 / 1440.0;
+
+/// Standard atmospheric refraction at the horizon in degrees, with its sign
+const HORIZON_REFRACTION: f64 = -0.5667;
+/// Mean radius of the Moon in AU (1737.4 km)
+const MOON_RADIUS: f64 = 1737.4 / 149597870.7;
+
+/// Rise, upper transit and set of a body within a 24 hour window
+#[derive(Debug, Clone, Copy)]
+pub struct RiseTransitSet {
+    /// First time the body rises above the horizon
+    /// => None if it does not rise within the window
+    pub rise: Option<Time>,
+    /// Highest culmination of the body
+    /// => None if it does not culminate within the window
+    pub transit: Option<Transit>,
+    /// First time the body sets below the horizon
+    /// => None if it does not set within the window
+    pub set: Option<Time>,
+    pub visibility: Visibility,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Transit {
+    pub time: Time,
+    /// Altitude at transit in degrees
+    pub altitude: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    /// The body crosses the horizon at least once within the window
+    RisesAndSets,
+    /// The body stays above the horizon for the whole window
+    Circumpolar,
+    /// The body stays below the horizon for the whole window
+    NeverRises,
+}
+
+/// Searches the 24 hours following `start` for rise, upper transit and set
+/// => Pass local midnight (in UTC) to get the events of a local calendar day
+/// => Satellites are refused, their passes are shorter than the scan step, see `satellite_passes`
+#[instrument(skip_all, name = "skyseeker::rise_transit_set")]
+pub fn rise_transit_set(
+    body: &CelestialBody,
+    observer: &Observer,
+    start: &Time,
+    earth_orientation: &EarthOrientation,
+) -> CoreResult<RiseTransitSet> {
+    if let CelestialBody::Satellite(satellite) = body {
+        return Err(CoreError::SatelliteRiseSet(satellite.id.clone()));
+    }
+
+    // Height above the standard altitude, which makes the horizon a fixed threshold of 0°
+    let mut altitude_at = |julian_day: f64| -> CoreResult<f64> {
+        let time = Time::from_julian_day(julian_day)?;
+        let position = body.position(observer, &time, earth_orientation)?;
+        Ok(position.geometric_altitude - standard_altitude(body, &position))
+    };
+
+    let start_day = start.get_julian_day()?;
+    let scan = Scan::new(start_day, start_day + 1.0, SCAN_STEP, &mut altitude_at)?;
+
+    let crossings = scan.crossings(0.0, &mut altitude_at)?;
+    let rise = crossings.iter().find(|crossing| crossing.rising);
+    let set = crossings.iter().find(|crossing| !crossing.rising);
+    let transit = match scan.maximum(&mut altitude_at)? {
+        Some((julian_day, _)) => {
+            let time = Time::from_julian_day(julian_day)?;
+            Some(Transit {
+                time,
+                altitude: body.position(observer, &time, earth_orientation)?.altitude,
+            })
+        }
+        None => None,
+    };
+
+    let visibility = if scan.all_above(0.0) {
+        Visibility::Circumpolar
+    } else if scan.all_below(0.0) {
+        Visibility::NeverRises
+    } else {
+        Visibility::RisesAndSets
+    };
+
+    Ok(RiseTransitSet {
+        rise: rise
+            .map(|crossing| Time::from_julian_day(crossing.julian_day))
+            .transpose()?,
+        transit,
+        set: set
+            .map(|crossing| Time::from_julian_day(crossing.julian_day))
+            .transpose()?,
+        visibility,
+    })
+}
+
+/// Geometric altitude of the body's center in degrees at the moment it rises or sets
+/// => Standard refraction at the horizon (34') for all bodies
+/// => The Sun's and the Moon's semidiameter so that rise and set refer to the upper limb
+/// => Parallax is already part of the topocentric position
+fn standard_altitude(body: &CelestialBody, position: &Position) -> f64 {
+    match body {
+        CelestialBody::Star(_)
+        | CelestialBody::DeepSky(_)
+        | CelestialBody::Planet(_)
+        | CelestialBody::MinorBody(_)
+        | CelestialBody::Satellite(_) => HORIZON_REFRACTION,
+        CelestialBody::Sun => -0.8333,
+        CelestialBody::Moon => match position.distance {
+            Some(Distance::AstronomicalUnits(distance)) => {
+                HORIZON_REFRACTION - (MOON_RADIUS / distance).asin().to_degrees()
+            }
+            _ => HORIZON_REFRACTION,
+        },
+    }
+}
@@ -0,0 +1,99 @@
+use crate::celestial_body::satellite::Satellite;
+use crate::error::CoreResult;
+use crate::events::Scan;
+use crate::position::astrometry::Astrometry;
+use crate::position::earth_orientation::EarthOrientation;
+use crate::position::observer::Observer;
+use crate::position::time::Time;
+use tracing::instrument;
+
+/// Step between altitude samples in days
+/// => 30 seconds, short enough to not miss low passes of satellites in low Earth orbit
+const SCAN_STEP: f64 = 30.0 / 86400.0;
+/// Step between altitude samples within a pass when looking for its highest point
+const PASS_SCAN_STEP: f64 = 5.0 / 86400.0;
+
+/// One pass of a satellite over the observer's horizon
+#[derive(Debug, Clone, Copy)]
+pub struct SatellitePass {
+    /// Acquisition of signal, the satellite rises above the horizon
+    pub aos: Time,
+    /// Azimuth at AOS in degrees
+    pub aos_azimuth: f64,
+    /// Time of closest approach, the highest point of the pass
+    pub tca: Time,
+    /// Altitude at TCA in degrees
+    pub max_elevation: f64,
+    /// Azimuth at TCA in degrees
+    pub tca_azimuth: f64,
+    /// Whether the satellite is lit by the Sun at TCA
+    pub sunlit: bool,
+    /// Loss of signal, the satellite sets below the horizon
+    pub los: Time,
+    /// Azimuth at LOS in degrees
+    pub los_azimuth: f64,
+}
+
+/// Searches for passes between `start` and `end`
+/// => Only complete passes are returned, one already in progress at `start` or not finished at `end` is left out
+#[instrument(skip_all, name = "skyseeker::satellite_passes")]
+pub fn satellite_passes(
+    satellite: &Satellite,
+    observer: &Observer,
+    start: &Time,
+    end: &Time,
+    earth_orientation: &EarthOrientation,
+) -> CoreResult<Vec<SatellitePass>> {
+    let state_at = |julian_day: f64| {
+        let time = Time::from_julian_day(julian_day)?;
+        let astrometry = Astrometry::new(observer, &time, earth_orientation)?;
+        satellite.state_with(&astrometry)
+    };
+    let mut altitude_at =
+        |julian_day: f64| -> CoreResult<f64> { Ok(state_at(julian_day)?.position.altitude) };
+
+    let scan = Scan::new(
+        start.get_julian_day()?,
+        end.get_julian_day()?,
+        SCAN_STEP,
+        &mut altitude_at,
+    )?;
+    let crossings = scan.crossings(0.0, &mut altitude_at)?;
+
+    let mut passes = Vec::new();
+    for pair in crossings.windows(2) {
+        let (rise, set) = (pair[0], pair[1]);
+        if !rise.rising || set.rising {
+            continue;
+        }
+
+        let pass_scan = Scan::new(
+            rise.julian_day,
+            set.julian_day,
+            PASS_SCAN_STEP,
+            &mut altitude_at,
+        )?;
+        let tca = match pass_scan.maximum(&mut altitude_at)? {
+            Some((julian_day, _)) => julian_day,
+            // Too short to have a sampled maximum inside, the middle is close enough
+            None => (rise.julian_day + set.julian_day) / 2.0,
+        };
+
+        let aos_state = state_at(rise.julian_day)?;
+        let tca_state = state_at(tca)?;
+        let los_state = state_at(set.julian_day)?;
+
+        passes.push(SatellitePass {
+            aos: Time::from_julian_day(rise.julian_day)?,
+            aos_azimuth: aos_state.position.azimuth,
+            tca: Time::from_julian_day(tca)?,
+            max_elevation: tca_state.position.altitude,
+            tca_azimuth: tca_state.position.azimuth,
+            sunlit: tca_state.sunlit,
+            los: Time::from_julian_day(set.julian_day)?,
+            los_azimuth: los_state.position.azimuth,
+        });
+    }
+
+    Ok(passes)
+}
@@ -0,0 +1,135 @@
+use crate::celestial_body::sun::sun_position;
+use crate::error::CoreResult;
+use crate::events::Scan;
+use crate::position::astrometry::Astrometry;
+use crate::position::earth_orientation::EarthOrientation;
+use crate::position::observer::Observer;
+use crate::position::time::Time;
+use tracing::instrument;
+
+/// Step between Sun altitude samples in days
+/// => 10 minutes, the Sun moves at most ~2.5° in that time
+const SCAN_STEP: f64 = 10.0 / 1440.0;
+
+/// Geometric altitude of the Sun's center at sunrise and sunset in degrees
+/// => Upper limb touching the horizon, including standard refraction (34') and semidiameter (16')
+pub const SUNRISE_ALTITUDE: f64 = -0.8333;
+/// Geometric altitude of the Sun's center at the end of civil twilight in degrees
+pub const CIVIL_TWILIGHT_ALTITUDE: f64 = -6.0;
+/// Geometric altitude of the Sun's center at the end of nautical twilight in degrees
+pub const NAUTICAL_TWILIGHT_ALTITUDE: f64 = -12.0;
+/// Geometric altitude of the Sun's center at the end of astronomical twilight in degrees
+/// => Below this the sky is fully dark
+pub const ASTRONOMICAL_TWILIGHT_ALTITUDE: f64 = -18.0;
+
+/// Twilight boundaries of one night
+#[derive(Debug, Clone, Copy)]
+pub struct Twilight {
+    /// Sunset and sunrise
+    pub sun: TwilightBoundary,
+    pub civil: TwilightBoundary,
+    pub nautical: TwilightBoundary,
+    /// Start and end of full darkness
+    pub astronomical: TwilightBoundary,
+}
+
+/// The evening and morning crossing of one twilight altitude
+#[derive(Debug, Clone, Copy)]
+pub struct TwilightBoundary {
+    /// Altitude of the boundary in degrees
+    pub altitude: f64,
+    /// The Sun descends below the altitude in the evening
+    /// => None if it does not within the window
+    pub dusk: Option<Time>,
+    /// The Sun rises above the altitude in the morning, after dusk if there is one
+    /// => None if it does not within the window
+    pub dawn: Option<Time>,
+    pub condition: SunCondition,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SunCondition {
+    /// The Sun crosses the altitude at least once within the window
+    Crosses,
+    /// The Sun stays above the altitude for the whole window
+    /// => Polar day for the sunset boundary, white nights for the twilight boundaries
+    AlwaysAbove,
+    /// The Sun stays below the altitude for the whole window
+    /// => Polar night for the sunset boundary
+    AlwaysBelow,
+}
+
+impl TwilightBoundary {
+    /// Whether the Sun is below this boundary's altitude at the given time
+    /// => Only meaningful for times within the searched window
+    pub fn is_below_at(&self, time: &Time) -> CoreResult<bool> {
+        match self.condition {
+            SunCondition::AlwaysAbove => Ok(false),
+            SunCondition::AlwaysBelow => Ok(true),
+            SunCondition::Crosses => {
+                let julian_day = time.get_julian_day()?;
+                let after_dusk = match self.dusk {
+                    Some(dusk) => julian_day >= dusk.get_julian_day()?,
+                    None => true,
+                };
+                let before_dawn = match self.dawn {
+                    Some(dawn) => julian_day < dawn.get_julian_day()?,
+                    None => true,
+                };
+                Ok(after_dusk && before_dawn)
+            }
+        }
+    }
+}
+
+/// Searches the 24 hours following `start` for the twilight boundaries
+/// => Pass local noon (in UTC) to get the night following that day
+#[instrument(skip_all, name = "skyseeker::twilight")]
+pub fn twilight(
+    observer: &Observer,
+    start: &Time,
+    earth_orientation: &EarthOrientation,
+) -> CoreResult<Twilight> {
+    let mut altitude_at = |julian_day: f64| -> CoreResult<f64> {
+        let time = Time::from_julian_day(julian_day)?;
+        let astrometry = Astrometry::new(observer, &time, earth_orientation)?;
+        Ok(sun_position(&astrometry).geometric_altitude)
+    };
+
+    let start_day = start.get_julian_day()?;
+    let scan = Scan::new(start_day, start_day + 1.0, SCAN_STEP, &mut altitude_at)?;
+
+    let mut boundary = |altitude: f64| -> CoreResult<TwilightBoundary> {
+        let crossings = scan.crossings(altitude, &mut altitude_at)?;
+        let dusk = crossings.iter().find(|crossing| !crossing.rising);
+        let dawn = crossings.iter().find(|crossing| {
+            crossing.rising && dusk.is_none_or(|dusk| crossing.julian_day > dusk.julian_day)
+        });
+
+        let condition = if scan.all_above(altitude) {
+            SunCondition::AlwaysAbove
+        } else if scan.all_below(altitude) {
+            SunCondition::AlwaysBelow
+        } else {
+            SunCondition::Crosses
+        };
+
+        Ok(TwilightBoundary {
+            altitude,
+            dusk: dusk
+                .map(|crossing| Time::from_julian_day(crossing.julian_day))
+                .transpose()?,
+            dawn: dawn
+                .map(|crossing| Time::from_julian_day(crossing.julian_day))
+                .transpose()?,
+            condition,
+        })
+    };
+
+    Ok(Twilight {
+        sun: boundary(SUNRISE_ALTITUDE)?,
+        civil: boundary(CIVIL_TWILIGHT_ALTITUDE)?,
+        nautical: boundary(NAUTICAL_TWILIGHT_ALTITUDE)?,
+        astronomical: boundary(ASTRONOMICAL_TWILIGHT_ALTITUDE)?,
+    })
+}
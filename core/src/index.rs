@@ -0,0 +1,369 @@
+use crate::celestial_body::CelestialBody;
+use crate::error::{CoreError, CoreResult};
+use sofars::vm::{pdp, pm, pn, pxp, s2c};
+use std::f64::consts::{FRAC_PI_4, PI};
+use tracing::instrument;
+
+/// Cells along each edge of a cube face
+/// => 6 × 32² = 6144 cells of ~2.6° across, a few stars per cell for the BSC5
+const RESOLUTION: usize = 32;
+
+/// Spatial index of the bodies fixed on the sky, by their J2000.0 catalog place
+/// => The sphere is split into the faces of a cube, each one into a grid of cells
+/// => The grid is warped with an arctangent so cells have about the same area, and its lines stay great circles
+/// => Solar system bodies and satellites move and are not indexed, see `CelestialBody::catalog_coordinates`
+#[derive(Debug)]
+pub struct SkyIndex {
+    cells: Vec<Cell>,
+    /// Largest angle from the center of any cell to a point of that cell in radians
+    cell_radius: f64,
+    len: usize,
+}
+
+#[derive(Debug)]
+struct Cell {
+    center: [f64; 3],
+    /// Sorted by visual magnitude, brightest first
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    body: CelestialBody,
+    direction: [f64; 3],
+    visual_magnitude: f64,
+}
+
+/// A body found by a search of the index
+#[derive(Debug, Clone, Copy)]
+pub struct IndexMatch<'a> {
+    pub body: &'a CelestialBody,
+    /// Angular distance from the center of the search in degrees
+    pub separation: f64,
+    pub visual_magnitude: f64,
+}
+
+impl Default for SkyIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SkyIndex {
+    pub fn new() -> Self {
+        let mut cells = Vec::with_capacity(6 * RESOLUTION * RESOLUTION);
+        let mut cell_radius: f64 = 0.0;
+        for face in 0..6 {
+            for i in 0..RESOLUTION {
+                for j in 0..RESOLUTION {
+                    let center = face_direction(
+                        face,
+                        grid_position(i as f64 + 0.5),
+                        grid_position(j as f64 + 0.5),
+                    );
+                    // Cell edges are great circles, so the corners are the farthest points
+                    let radius = [(i, j), (i + 1, j), (i, j + 1), (i + 1, j + 1)]
+                        .into_iter()
+                        .map(|(u, v)| {
+                            let corner = face_direction(
+                                face,
+                                grid_position(u as f64),
+                                grid_position(v as f64),
+                            );
+                            angle_between(&center, &corner)
+                        })
+                        .fold(0.0, f64::max);
+                    cell_radius = cell_radius.max(radius);
+                    cells.push(Cell {
+                        center,
+                        entries: Vec::new(),
+                    });
+                }
+            }
+        }
+        Self {
+            cells,
+            cell_radius,
+            len: 0,
+        }
+    }
+
+    #[instrument(skip_all, name = "skyseeker::index::from_bodies")]
+    pub fn from_bodies<'a>(bodies: impl IntoIterator<Item = &'a CelestialBody>) -> Self {
+        let mut index = Self::new();
+        for body in bodies {
+            index.insert(body);
+        }
+        index
+    }
+
+    /// Number of indexed bodies
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds a body, returns false if it has no catalog place and cannot be indexed
+    pub fn insert(&mut self, body: &CelestialBody) -> bool {
+        let Some((right_ascension, declination)) = body.catalog_coordinates() else {
+            return false;
+        };
+        let Some(visual_magnitude) = catalog_magnitude(body) else {
+            return false;
+        };
+
+        let direction = s2c(right_ascension, declination);
+        let entries = &mut self.cells[cell_of(&direction)].entries;
+        let at = entries.partition_point(|entry| entry.visual_magnitude <= visual_magnitude);
+        entries.insert(
+            at,
+            Entry {
+                body: body.clone(),
+                direction,
+                visual_magnitude,
+            },
+        );
+        self.len += 1;
+        true
+    }
+
+    /// Removes a body by its id, returns false if it was not indexed
+    /// => The body has to have the catalog place it was indexed with
+    pub fn remove(&mut self, body: &CelestialBody) -> bool {
+        let Some((right_ascension, declination)) = body.catalog_coordinates() else {
+            return false;
+        };
+        let entries = &mut self.cells[cell_of(&s2c(right_ascension, declination))].entries;
+        let Some(at) = entries
+            .iter()
+            .position(|entry| entry.body.id() == body.id())
+        else {
+            return false;
+        };
+        entries.remove(at);
+        self.len -= 1;
+        true
+    }
+
+    /// Bodies within `radius` of a J2000.0 place and not fainter than `max_magnitude`, closest first
+    /// => Angles in degrees, pass f64::INFINITY as `max_magnitude` to get all of them
+    #[instrument(skip_all, name = "skyseeker::index::cone_search")]
+    pub fn cone_search(
+        &self,
+        right_ascension: f64,
+        declination: f64,
+        radius: f64,
+        max_magnitude: f64,
+    ) -> Vec<IndexMatch<'_>> {
+        let center = s2c(right_ascension.to_radians(), declination.to_radians());
+        self.cone_search_direction(&center, radius.to_radians(), max_magnitude)
+    }
+
+    /// Bodies inside a convex polygon of J2000.0 places and not fainter than `max_magnitude`, brightest first
+    /// => Vertices in degrees, in either order around the polygon, e.g. the corners of a field of view
+    /// => The edges are great circles, the polygon has to be smaller than a hemisphere
+    /// => The separation of each match is measured from the center of the polygon
+    #[instrument(skip_all, name = "skyseeker::index::polygon_search")]
+    pub fn polygon_search(
+        &self,
+        vertices: &[(f64, f64)],
+        max_magnitude: f64,
+    ) -> CoreResult<Vec<IndexMatch<'_>>> {
+        if vertices.len() < 3 {
+            return Err(CoreError::SearchPolygon(vertices.len()));
+        }
+        let corners: Vec<[f64; 3]> = vertices
+            .iter()
+            .map(|(right_ascension, declination)| {
+                s2c(right_ascension.to_radians(), declination.to_radians())
+            })
+            .collect();
+
+        let (_, center) = pn(&corners.iter().fold([0.0; 3], |sum, corner| {
+            [sum[0] + corner[0], sum[1] + corner[1], sum[2] + corner[2]]
+        }));
+        let radius = corners
+            .iter()
+            .map(|corner| angle_between(&center, corner))
+            .fold(0.0, f64::max);
+
+        // Normals of the edges' great circles, all pointing into the polygon
+        let mut normals: Vec<[f64; 3]> = corners
+            .iter()
+            .zip(corners.iter().cycle().skip(1))
+            .map(|(a, b)| pxp(a, b))
+            .collect();
+        if pdp(&normals[0], &center) < 0.0 {
+            normals
+                .iter_mut()
+                .for_each(|normal| *normal = normal.map(|component| -component));
+        }
+
+        let mut matches: Vec<IndexMatch<'_>> = self
+            .cone_search_direction(&center, radius, max_magnitude)
+            .into_iter()
+            .filter(|found| {
+                let Some((right_ascension, declination)) = found.body.catalog_coordinates() else {
+                    return false;
+                };
+                let direction = s2c(right_ascension, declination);
+                normals.iter().all(|normal| pdp(normal, &direction) >= 0.0)
+            })
+            .collect();
+        matches.sort_by(|a, b| a.visual_magnitude.total_cmp(&b.visual_magnitude));
+        Ok(matches)
+    }
+
+    /// The `count` bodies closest to a J2000.0 place and not fainter than `max_magnitude`, closest first
+    /// => Angles in degrees
+    #[instrument(skip_all, name = "skyseeker::index::nearest")]
+    pub fn nearest(
+        &self,
+        right_ascension: f64,
+        declination: f64,
+        count: usize,
+        max_magnitude: f64,
+    ) -> Vec<IndexMatch<'_>> {
+        if count == 0 {
+            return Vec::new();
+        }
+        let center = s2c(right_ascension.to_radians(), declination.to_radians());
+
+        // Widen the cone until it holds enough bodies, the closest ones are then all inside
+        let mut radius = self.cell_radius;
+        loop {
+            let mut matches = self.cone_search_direction(&center, radius, max_magnitude);
+            if matches.len() >= count || radius >= PI {
+                matches.truncate(count);
+                return matches;
+            }
+            radius = (radius * 2.0).min(PI);
+        }
+    }
+
+    /// Cone search around a unit vector, radius in radians
+    pub(crate) fn cone_search_direction(
+        &self,
+        center: &[f64; 3],
+        radius: f64,
+        max_magnitude: f64,
+    ) -> Vec<IndexMatch<'_>> {
+        let min_cosine = radius.cos();
+        let min_cell_cosine = (radius + self.cell_radius).min(PI).cos();
+        let mut matches = Vec::new();
+        for cell in &self.cells {
+            if pdp(center, &cell.center) < min_cell_cosine {
+                continue;
+            }
+            for entry in &cell.entries {
+                if entry.visual_magnitude > max_magnitude {
+                    break;
+                }
+                if pdp(center, &entry.direction) >= min_cosine {
+                    matches.push(IndexMatch {
+                        body: &entry.body,
+                        separation: angle_between(center, &entry.direction).to_degrees(),
+                        visual_magnitude: entry.visual_magnitude,
+                    });
+                }
+            }
+        }
+        matches.sort_by(|a, b| a.separation.total_cmp(&b.separation));
+        matches
+    }
+}
+
+/// Magnitude of a body fixed on the sky, it does not change with time
+fn catalog_magnitude(body: &CelestialBody) -> Option<f64> {
+    match body {
+        CelestialBody::Star(star) => Some(star.visual_magnitude),
+        CelestialBody::DeepSky(deep_sky) => Some(deep_sky.magnitude()),
+        _ => None,
+    }
+}
+
+/// Index of the cell containing a unit vector
+fn cell_of(direction: &[f64; 3]) -> usize {
+    let axis = (0..3)
+        .max_by(|a, b| direction[*a].abs().total_cmp(&direction[*b].abs()))
+        .unwrap_or(0);
+    let face = 2 * axis + usize::from(direction[axis] < 0.0);
+    let major = direction[axis].abs();
+    let grid_index = |component: f64| {
+        let warped = (component / major).atan() / FRAC_PI_4;
+        (((warped + 1.0) / 2.0 * RESOLUTION as f64) as usize).min(RESOLUTION - 1)
+    };
+    let i = grid_index(direction[(axis + 1) % 3]);
+    let j = grid_index(direction[(axis + 2) % 3]);
+    (face * RESOLUTION + i) * RESOLUTION + j
+}
+
+/// Tangent plane coordinate of a grid line, from 0 to RESOLUTION
+fn grid_position(line: f64) -> f64 {
+    (FRAC_PI_4 * (2.0 * line / RESOLUTION as f64 - 1.0)).tan()
+}
+
+/// Unit vector of a point on a cube face from its tangent plane coordinates
+fn face_direction(face: usize, u: f64, v: f64) -> [f64; 3] {
+    let axis = face / 2;
+    let sign = if face.is_multiple_of(2) { 1.0 } else { -1.0 };
+    let mut direction = [0.0; 3];
+    direction[axis] = sign;
+    direction[(axis + 1) % 3] = u;
+    direction[(axis + 2) % 3] = v;
+    pn(&direction).1
+}
+
+/// Angle between two unit vectors in radians, accurate for tiny angles too
+fn angle_between(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    pm(pxp(a, b)).atan2(pdp(a, b))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::celestial_body::star::Star;
+    use crate::celestial_body::CelestialBody;
+    use crate::Skyseeker;
+    use std::sync::Arc;
+
+    fn star(visual_magnitude: f64) -> CelestialBody {
+        CelestialBody::Star(Arc::new(Star {
+            id: "HR 1".to_string(),
+            hr: Some(1),
+            name: None,
+            common_name: None,
+            bayer: None,
+            bayer_full: None,
+            constellation: None,
+            notes: Vec::new(),
+            right_ascension: 1.0,
+            declination: 0.5,
+            proper_motion_right_ascension: 0.0,
+            proper_motion_declination: 0.0,
+            parallax: 0.0,
+            radial_velocity: 0.0,
+            visual_magnitude,
+            b_v_color: None,
+        }))
+    }
+
+    #[test]
+    fn reloading_a_body_replaces_its_index_entry() {
+        let mut skyseeker = Skyseeker::new();
+        skyseeker.load_body(star(5.0));
+        skyseeker.load_body(star(2.0));
+
+        let matches = skyseeker.sky_index().cone_search(
+            1.0_f64.to_degrees(),
+            0.5_f64.to_degrees(),
+            0.1,
+            f64::INFINITY,
+        );
+        assert_eq!(skyseeker.sky_index().len(), 1);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].visual_magnitude, 2.0);
+    }
+}
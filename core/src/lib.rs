@@ -1,18 +1,44 @@
+use crate::celestial_body::moon::MoonPhase;
+use crate::constellation::{Constellation, ConstellationBoundaries, ConstellationFigure};
 use crate::error::{CoreError, CoreResult};
-use crate::position::Position;
+use crate::events::conjunction::Conjunction;
+use crate::events::eclipse::Eclipse;
+use crate::events::occultation::Occultation;
+use crate::events::rise_set::RiseTransitSet;
+use crate::events::satellite_pass::SatellitePass;
+use crate::events::twilight::Twilight;
+use crate::index::SkyIndex;
+use crate::position::astrometry::Astrometry;
+use crate::position::{Position, Separation};
+use crate::query::{MovingBodies, NearbyBody, SkyDirection};
+use crate::search::{SearchIndex, SearchMatch};
 use position::{earth_orientation, observer, time};
 use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
 use tracing::instrument;
 
 pub mod celestial_body;
 pub mod codec;
+pub mod constellation;
 pub mod error;
+pub mod events;
+pub mod index;
 pub mod math;
 pub mod position;
+pub mod query;
+pub mod search;
 
 #[derive(Debug, Default)]
 pub struct Skyseeker {
     pub bodies_by_id: HashMap<String, celestial_body::CelestialBody>,
+    /// Bodies fixed on the sky by their catalog place, kept in step by `load_body`
+    sky_index: SkyIndex,
+    /// Names and designations of all bodies, kept in step by `load_body`
+    search_index: SearchIndex,
+    constellation_boundaries: ConstellationBoundaries,
+    constellation_figures: Vec<ConstellationFigure>,
+    /// Coarse motion of the bodies without a catalog place, cleared by `load_body`
+    moving_bodies: Mutex<MovingBodies>,
 }
 
 impl Skyseeker {
@@ -21,8 +47,17 @@ impl Skyseeker {
     }
 
     pub fn load_body(&mut self, body: celestial_body::CelestialBody) {
-        let id = body.id().to_string();
-        self.bodies_by_id.insert(id, body);
+        // The replaced body goes first, the index removes by id and would otherwise find the new one
+        if let Some(replaced) = self.bodies_by_id.remove(body.id()) {
+            self.sky_index.remove(&replaced);
+        }
+        self.sky_index.insert(&body);
+        self.search_index.insert(&body);
+        *self
+            .moving_bodies
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner) = MovingBodies::default();
+        self.bodies_by_id.insert(body.id().to_string(), body);
     }
 
     pub fn load_bodies(&mut self, new_bodies: Vec<celestial_body::CelestialBody>) {
@@ -35,6 +70,14 @@ impl Skyseeker {
         self.load_bodies(celestial_body::CelestialBody::standard_bodies());
     }
 
+    pub fn load_constellation_boundaries(&mut self, boundaries: ConstellationBoundaries) {
+        self.constellation_boundaries = boundaries;
+    }
+
+    pub fn load_constellation_figures(&mut self, figures: Vec<ConstellationFigure>) {
+        self.constellation_figures = figures;
+    }
+
     pub fn get_body(&self, body_id: impl AsRef<str>) -> Option<&celestial_body::CelestialBody> {
         self.bodies_by_id.get(body_id.as_ref())
     }
@@ -43,6 +86,133 @@ impl Skyseeker {
         self.bodies_by_id.values()
     }
 
+    /// Spatial index of the loaded stars and deep-sky objects
+    pub fn sky_index(&self) -> &SkyIndex {
+        &self.sky_index
+    }
+
+    /// Bodies whose name or designation matches a free text query, best match first
+    /// => See `SearchIndex::search` for how matches are ranked
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchMatch<'_>> {
+        self.search_index
+            .search(query, limit)
+            .into_iter()
+            .filter_map(|(id, matched_text)| {
+                Some(SearchMatch {
+                    body: self.get_body(id)?,
+                    matched_text: matched_text.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// Bodies within `radius` degrees of a direction on the observer's sky, closest first
+    pub fn bodies_near(
+        &self,
+        direction: &SkyDirection,
+        radius: f64,
+        observer: &observer::Observer,
+        time: &time::Time,
+        earth_orientation: &earth_orientation::EarthOrientation,
+    ) -> CoreResult<Vec<NearbyBody<'_>>> {
+        let astrometry = Astrometry::new(observer, time, earth_orientation)?;
+        self.bodies_near_with(direction, radius, &astrometry)
+    }
+
+    /// Bodies within `radius` degrees of a direction, using already set up astrometry parameters
+    /// => Meant for running every frame, e.g. on mouse hover, with the astrometry the sky was placed with
+    /// => Moving bodies are first compared by their motion over a time step, kept from query to query
+    pub fn bodies_near_with(
+        &self,
+        direction: &SkyDirection,
+        radius: f64,
+        astrometry: &Astrometry,
+    ) -> CoreResult<Vec<NearbyBody<'_>>> {
+        let time = time::Time::from_julian_day(astrometry.julian_day())?;
+        let icrs_direction = query::icrs_direction(direction, astrometry);
+        let catalog_matches = self.sky_index.cone_search_direction(
+            &icrs_direction,
+            (radius + query::CATALOG_MARGIN).to_radians(),
+            f64::INFINITY,
+        );
+        let moving_ids = self
+            .moving_bodies
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .candidates(
+                self.bodies_by_id.values(),
+                &icrs_direction,
+                radius,
+                astrometry,
+            );
+        let bodies = catalog_matches
+            .iter()
+            .map(|found| found.body)
+            .chain(moving_ids.iter().filter_map(|id| self.get_body(id)));
+        query::bodies_near(bodies, direction, radius, astrometry, &time)
+    }
+
+    pub fn constellation_boundaries(&self) -> &ConstellationBoundaries {
+        &self.constellation_boundaries
+    }
+
+    pub fn constellation_figures(&self) -> &[ConstellationFigure] {
+        &self.constellation_figures
+    }
+
+    /// Constellation containing a J2000.0 (ICRS) place, angles in degrees
+    /// => None until the boundaries are loaded with `load_constellation_boundaries`
+    pub fn constellation_at(
+        &self,
+        right_ascension: f64,
+        declination: f64,
+    ) -> Option<&'static Constellation> {
+        self.constellation_boundaries
+            .find(right_ascension, declination)
+    }
+
+    /// Constellation containing a direction on the observer's sky
+    pub fn constellation_in_direction(
+        &self,
+        direction: &SkyDirection,
+        observer: &observer::Observer,
+        time: &time::Time,
+        earth_orientation: &earth_orientation::EarthOrientation,
+    ) -> CoreResult<Option<&'static Constellation>> {
+        let astrometry = position::astrometry::Astrometry::new(observer, time, earth_orientation)?;
+        Ok(self
+            .constellation_boundaries
+            .find_direction(&query::icrs_direction(direction, &astrometry)))
+    }
+
+    /// Constellation a body is in at a given time
+    /// => Bodies fixed on the sky go by their catalog place, the others by where the observer sees them
+    pub fn constellation(
+        &self,
+        body_id: impl AsRef<str>,
+        observer: &observer::Observer,
+        time: &time::Time,
+        earth_orientation: &earth_orientation::EarthOrientation,
+    ) -> CoreResult<Option<&'static Constellation>> {
+        let Some(body) = self.get_body(&body_id) else {
+            return Err(CoreError::BodyNotFound(body_id.as_ref().to_string()));
+        };
+        if let Some((right_ascension, declination)) = body.catalog_coordinates() {
+            return Ok(
+                self.constellation_at(right_ascension.to_degrees(), declination.to_degrees())
+            );
+        }
+        let astrometry = position::astrometry::Astrometry::new(observer, time, earth_orientation)?;
+        let position = body.position_with(&astrometry)?;
+        let direction = SkyDirection::Equatorial {
+            right_ascension: position.right_ascension,
+            declination: position.declination,
+        };
+        Ok(self
+            .constellation_boundaries
+            .find_direction(&query::icrs_direction(&direction, &astrometry)))
+    }
+
     #[instrument(skip_all, name = "skyseeker::position")]
     pub fn position(
         &self,
@@ -56,4 +226,126 @@ impl Skyseeker {
         };
         body.position(observer, time, earth_orientation)
     }
+
+    pub fn rise_transit_set(
+        &self,
+        body_id: impl AsRef<str>,
+        observer: &observer::Observer,
+        start: &time::Time,
+        earth_orientation: &earth_orientation::EarthOrientation,
+    ) -> CoreResult<RiseTransitSet> {
+        let Some(body) = self.get_body(&body_id) else {
+            return Err(CoreError::BodyNotFound(body_id.as_ref().to_string()));
+        };
+        body.rise_transit_set(observer, start, earth_orientation)
+    }
+
+    /// Separation and position angle of body `b` as seen from body `a`
+    pub fn separation(
+        &self,
+        a: impl AsRef<str>,
+        b: impl AsRef<str>,
+        observer: &observer::Observer,
+        time: &time::Time,
+        earth_orientation: &earth_orientation::EarthOrientation,
+    ) -> CoreResult<Separation> {
+        let (body_a, body_b) = self.get_body_pair(&a, &b)?;
+        events::conjunction::separation(body_a, body_b, observer, time, earth_orientation)
+    }
+
+    /// Closest approaches of two bodies between `start` and `end` that come within `max_separation` degrees
+    #[allow(clippy::too_many_arguments)]
+    pub fn conjunctions(
+        &self,
+        a: impl AsRef<str>,
+        b: impl AsRef<str>,
+        observer: &observer::Observer,
+        start: &time::Time,
+        end: &time::Time,
+        max_separation: f64,
+        earth_orientation: &earth_orientation::EarthOrientation,
+    ) -> CoreResult<Vec<Conjunction>> {
+        let (body_a, body_b) = self.get_body_pair(&a, &b)?;
+        events::conjunction::conjunctions(
+            body_a,
+            body_b,
+            observer,
+            start,
+            end,
+            max_separation,
+            earth_orientation,
+        )
+    }
+
+    fn get_body_pair(
+        &self,
+        a: impl AsRef<str>,
+        b: impl AsRef<str>,
+    ) -> CoreResult<(
+        &celestial_body::CelestialBody,
+        &celestial_body::CelestialBody,
+    )> {
+        let Some(body_a) = self.get_body(&a) else {
+            return Err(CoreError::BodyNotFound(a.as_ref().to_string()));
+        };
+        let Some(body_b) = self.get_body(&b) else {
+            return Err(CoreError::BodyNotFound(b.as_ref().to_string()));
+        };
+        Ok((body_a, body_b))
+    }
+
+    pub fn satellite_passes(
+        &self,
+        body_id: impl AsRef<str>,
+        observer: &observer::Observer,
+        start: &time::Time,
+        end: &time::Time,
+        earth_orientation: &earth_orientation::EarthOrientation,
+    ) -> CoreResult<Vec<SatellitePass>> {
+        let Some(body) = self.get_body(&body_id) else {
+            return Err(CoreError::BodyNotFound(body_id.as_ref().to_string()));
+        };
+        let celestial_body::CelestialBody::Satellite(satellite) = body else {
+            return Err(CoreError::NotASatellite(body_id.as_ref().to_string()));
+        };
+        events::satellite_pass::satellite_passes(satellite, observer, start, end, earth_orientation)
+    }
+
+    pub fn twilight(
+        &self,
+        observer: &observer::Observer,
+        start: &time::Time,
+        earth_orientation: &earth_orientation::EarthOrientation,
+    ) -> CoreResult<Twilight> {
+        events::twilight::twilight(observer, start, earth_orientation)
+    }
+
+    pub fn eclipses(
+        &self,
+        observer: &observer::Observer,
+        start: &time::Time,
+        end: &time::Time,
+        earth_orientation: &earth_orientation::EarthOrientation,
+    ) -> CoreResult<Vec<Eclipse>> {
+        events::eclipse::eclipses(observer, start, end, earth_orientation)
+    }
+
+    /// Occultations of all loaded stars by the Moon between `start` and `end`
+    pub fn occultations(
+        &self,
+        observer: &observer::Observer,
+        start: &time::Time,
+        end: &time::Time,
+        earth_orientation: &earth_orientation::EarthOrientation,
+    ) -> CoreResult<Vec<Occultation>> {
+        let stars = self.iter_bodies().filter_map(|body| match body {
+            celestial_body::CelestialBody::Star(star) => Some(star.as_ref()),
+            _ => None,
+        });
+        events::occultation::occultations(stars, observer, start, end, earth_orientation)
+    }
+
+    pub fn moon_phase(&self, time: &time::Time) -> CoreResult<MoonPhase> {
+        celestial_body::moon::moon_phase(time)
+    }
 }
@@ -39,3 +39,24 @@ pub fn time_format_to_radians(
         },
     }
 }
+
+/// Great-circle separation and position angle of a second point as seen from the first, all in radians
+/// => Works for any pair of spherical coordinates (right ascension/declination, azimuth/altitude, ...)
+/// => The position angle is measured from the pole of the coordinate system towards increasing longitude
+pub fn angular_separation(
+    longitude_1: f64,
+    latitude_1: f64,
+    longitude_2: f64,
+    latitude_2: f64,
+) -> (f64, f64) {
+    let delta = longitude_2 - longitude_1;
+
+    // Vincenty's formula, accurate for both tiny and near antipodal separations
+    let east = latitude_2.cos() * delta.sin();
+    let north =
+        latitude_1.cos() * latitude_2.sin() - latitude_1.sin() * latitude_2.cos() * delta.cos();
+    let along =
+        latitude_1.sin() * latitude_2.sin() + latitude_1.cos() * latitude_2.cos() * delta.cos();
+
+    (east.hypot(north).atan2(along), east.atan2(north))
+}
@@ -1,9 +1,12 @@
+use crate::math::angular_separation;
+
+pub mod astrometry;
 pub mod earth_orientation;
 pub mod observer;
 pub mod time;
 
 /// The position of an object in the sky
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct Position {
     /// Azimuth in degrees
     /// => Compass direction: 0°=N, 90°=E, 180°=S, 270°=W
@@ -11,4 +14,73 @@ pub struct Position {
     /// Altitude in degrees
     /// => Angle above horizon: 0°=horizon, 90°=overhead
     pub altitude: f64,
+    /// Altitude without atmospheric refraction in degrees
+    /// => Refraction lifts objects by ~0.5° at the horizon, rise/set and twilight conventions are based on this value
+    pub geometric_altitude: f64,
+    /// Topocentric right ascension in degrees (0° to 360°)
+    /// => Apparent place referred to the true equator and equinox of date, without refraction
+    pub right_ascension: f64,
+    /// Topocentric declination in degrees (-90° to +90°)
+    /// => Apparent place referred to the true equator of date, without refraction
+    pub declination: f64,
+    /// Local hour angle in degrees (-180° to +180°)
+    /// => Negative east of the meridian (before transit), positive west of it
+    pub hour_angle: f64,
+    /// Distance from the observer, if known
+    pub distance: Option<Distance>,
+}
+
+impl Position {
+    /// Zenith distance in degrees
+    /// => Angle from the point straight overhead: 0°=overhead, 90°=horizon
+    pub fn zenith_distance(&self) -> f64 {
+        90.0 - self.altitude
+    }
+
+    /// Relative air mass along the line of sight (Kasten & Young, 1989)
+    /// => 1.0 at the zenith, ~38 at the horizon
+    /// => None if the object is below the horizon
+    pub fn airmass(&self) -> Option<f64> {
+        if self.altitude < 0.0 {
+            return None;
+        }
+        let zenith_distance = self.zenith_distance();
+        Some(
+            1.0 / (zenith_distance.to_radians().cos()
+                + 0.50572 * (96.07995 - zenith_distance).powf(-1.6364)),
+        )
+    }
+
+    /// Great-circle separation and position angle of another position, as seen from this one
+    /// => Based on the topocentric right ascension and declination, so without refraction
+    pub fn separation(&self, other: &Position) -> Separation {
+        let (separation, position_angle) = angular_separation(
+            self.right_ascension.to_radians(),
+            self.declination.to_radians(),
+            other.right_ascension.to_radians(),
+            other.declination.to_radians(),
+        );
+        Separation {
+            separation: separation.to_degrees(),
+            position_angle: position_angle.to_degrees().rem_euclid(360.0),
+        }
+    }
+}
+
+/// Angular relation between two positions on the sky
+#[derive(Debug, Clone, Copy)]
+pub struct Separation {
+    /// Great-circle distance in degrees
+    pub separation: f64,
+    /// Direction of the second position from the first in degrees
+    /// => Measured from celestial north through east (0°=N, 90°=E)
+    pub position_angle: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distance {
+    /// Used for solar system bodies
+    AstronomicalUnits(f64),
+    /// Derived from the parallax of stars
+    Parsecs(f64),
 }
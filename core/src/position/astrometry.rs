@@ -0,0 +1,316 @@
+use crate::error::{CoreError, CoreResult};
+use crate::position::earth_orientation::EarthOrientation;
+use crate::position::observer::Observer;
+use crate::position::time::Time;
+use crate::position::{Distance, Position};
+use sofars::astro::{ab, apco13, atciq, aticq, atioq, IauAstrom};
+use sofars::coords::gd2gc;
+use sofars::eph::epv00;
+use sofars::vm::{anp, c2s, pm, pmp, pn, rxp, s2c, sxp, trxp};
+use std::f64::consts::{FRAC_PI_2, PI};
+
+/// Astronomical unit in meters
+const ASTRONOMICAL_UNIT: f64 = 149_597_870_700.0;
+/// Zenith distance beyond which the refraction model is held constant
+/// => atioq does the same by keeping the sine of the altitude above 0.05
+const MAX_REFRACTION_ZENITH_DISTANCE: f64 = 87.134 * PI / 180.0;
+
+/// Rotation from ICRS to galactic coordinates, as in SOFA's icrs2g
+/// => Galactic pole and longitude origin of the Hipparcos catalog
+const ICRS_TO_GALACTIC: [[f64; 3]; 3] = [
+    [
+        -0.05487556041621537,
+        -0.873437090234885,
+        -0.4838350155487132,
+    ],
+    [0.49410942787558365, -0.4448296299600112, 0.7469822444972188],
+    [
+        -0.8676661490190047,
+        -0.19807637343120152,
+        0.4559837761750669,
+    ],
+];
+
+/// Star-independent astrometry parameters for one observer at one instant
+/// => Setting these up (Earth ephemeris, precession-nutation, refraction constants) is the expensive part
+/// => Applying them to a single position is cheap, so reuse them when placing many objects
+#[derive(Debug, Clone, Copy)]
+pub struct Astrometry {
+    astrom: IauAstrom,
+    /// Equation of the origins (ERA - GST) in radians
+    /// => Converts CIO-based right ascensions to equinox-based ones
+    equation_of_origins: f64,
+    /// Observer's geocentric position in meters, in the (-hour angle, declination) frame
+    /// => Shifts nearby solar system bodies by their topocentric parallax (almost 1° for the Moon)
+    observer_geocentric: [f64; 3],
+    /// UTC quasi Julian Date of the observation
+    julian_day: f64,
+    /// Julian Ephemeris Day (TT) of the observation
+    julian_ephemeris_day: f64,
+}
+
+impl Astrometry {
+    pub fn new(
+        observer: &Observer,
+        time: &Time,
+        earth_orientation: &EarthOrientation,
+    ) -> CoreResult<Self> {
+        let (utc1, utc2) = time.get_double_julian()?;
+        let julian_ephemeris_day = time.get_julian_ephemeris_day()?;
+        // apco13 panics where the Earth ephemeris gives up instead of reporting it
+        if epv00(julian_ephemeris_day, 0.0).is_none() {
+            return Err(CoreError::EarthEphemerisDate);
+        }
+
+        let mut observer_itrs = [0.0; 3];
+        if gd2gc(
+            1,
+            observer.longitude,
+            observer.latitude,
+            observer.height(),
+            &mut observer_itrs,
+        )
+        .is_err()
+        {
+            return Err(CoreError::ObserverLocation);
+        }
+        let observer_geocentric = [
+            observer_itrs[0].hypot(observer_itrs[1]),
+            0.0,
+            observer_itrs[2],
+        ];
+
+        let mut astrom = IauAstrom::default();
+        let mut equation_of_origins = 0.0;
+        match apco13(
+            utc1,
+            utc2,
+            earth_orientation.dut1,
+            observer.longitude,
+            observer.latitude,
+            observer.height(),
+            earth_orientation.polar_motion_x(),
+            earth_orientation.polar_motion_y(),
+            observer.pressure(),
+            observer.temperature(),
+            observer.humidity(),
+            observer.wavelength(),
+            &mut astrom,
+            &mut equation_of_origins,
+        ) {
+            Ok(_) => Ok(Self {
+                astrom,
+                equation_of_origins,
+                observer_geocentric,
+                julian_day: utc1 + utc2,
+                julian_ephemeris_day,
+            }),
+            Err(_) => Err(CoreError::AstrometryDate),
+        }
+    }
+
+    /// UTC quasi Julian Date of the observation
+    pub fn julian_day(&self) -> f64 {
+        self.julian_day
+    }
+
+    /// Julian Ephemeris Day (TT), the time argument of the solar system ephemerides
+    pub fn julian_ephemeris_day(&self) -> f64 {
+        self.julian_ephemeris_day
+    }
+
+    /// Observed position of an object from its ICRS catalog entry
+    /// => Angles in radians, proper motions in radians/year, parallax in arcseconds, radial velocity in km/s
+    pub fn observe_catalog(
+        &self,
+        right_ascension: f64,
+        declination: f64,
+        proper_motion_right_ascension: f64,
+        proper_motion_declination: f64,
+        parallax: f64,
+        radial_velocity: f64,
+    ) -> Position {
+        let mut astrom = self.astrom;
+        let (cirs_right_ascension, cirs_declination) = atciq(
+            right_ascension,
+            declination,
+            proper_motion_right_ascension,
+            proper_motion_declination,
+            parallax,
+            radial_velocity,
+            &mut astrom,
+        );
+
+        let distance = (parallax > 0.0).then(|| Distance::Parsecs(1.0 / parallax));
+        self.observe_cirs(cirs_right_ascension, cirs_declination, distance)
+    }
+
+    /// Observed position of a direction given on the true equator and equinox of date, angles in radians
+    /// => E.g. a point of the celestial equator, as the sky is at the moment
+    pub fn observe_apparent(&self, right_ascension: f64, declination: f64) -> Position {
+        self.observe_cirs(
+            right_ascension + self.equation_of_origins,
+            declination,
+            None,
+        )
+    }
+
+    /// Observed position of a direction given on the mean ecliptic and equinox of date, angles in radians
+    pub fn observe_ecliptic(&self, longitude: f64, latitude: f64) -> Position {
+        let (right_ascension, declination) = self.ecliptic_to_apparent(longitude, latitude);
+        self.observe_apparent(right_ascension, declination)
+    }
+
+    /// Right ascension and declination on the true equator and equinox of date, angles in radians
+    /// => From ecliptic coordinates on the mean ecliptic and equinox of date, as the astro crate gives them
+    /// => Nutation is applied here, so the inputs must not contain it already
+    pub(crate) fn ecliptic_to_apparent(&self, longitude: f64, latitude: f64) -> (f64, f64) {
+        let (nut_in_long, nut_in_oblq) = astro::nutation::nutation(self.julian_ephemeris_day);
+        let true_obliquity = astro::ecliptic::mn_oblq_IAU(self.julian_ephemeris_day) + nut_in_oblq;
+        let longitude = longitude + nut_in_long;
+
+        (
+            astro::coords::asc_frm_ecl(longitude, latitude, true_obliquity),
+            astro::coords::dec_frm_ecl(longitude, latitude, true_obliquity),
+        )
+    }
+
+    /// Observed position of a direction given in galactic coordinates, angles in radians
+    pub fn observe_galactic(&self, longitude: f64, latitude: f64) -> Position {
+        let mut icrs = [0.0; 3];
+        trxp(&ICRS_TO_GALACTIC, &s2c(longitude, latitude), &mut icrs);
+        let (right_ascension, declination) = c2s(&icrs);
+        self.observe_catalog(anp(right_ascension), declination, 0.0, 0.0, 0.0, 0.0)
+    }
+
+    /// Local apparent sidereal time in radians
+    pub fn local_sidereal_time(&self) -> f64 {
+        anp(self.astrom.eral - self.equation_of_origins)
+    }
+
+    /// Geocentric position of the observer in meters, referred to the true equator and equinox of date
+    pub(crate) fn observer_equatorial(&self) -> [f64; 3] {
+        let (sin_time, cos_time) = self.local_sidereal_time().sin_cos();
+        let [equatorial_distance, _, polar_distance] = self.observer_geocentric;
+        [
+            equatorial_distance * cos_time,
+            equatorial_distance * sin_time,
+            polar_distance,
+        ]
+    }
+
+    /// Heliocentric position of the observer in AU, referred to the ICRS axes
+    pub(crate) fn observer_heliocentric(&self) -> [f64; 3] {
+        sxp(self.astrom.em, &self.astrom.eh)
+    }
+
+    /// Observed position of a solar system body from its astrometric place relative to the observer
+    /// => Vector in AU referred to the ICRS axes, already corrected for light time
+    pub(crate) fn observe_topocentric(&self, astrometric: &[f64; 3]) -> Position {
+        let (distance, direction) = pn(astrometric);
+        let aberrated = ab(&direction, &self.astrom.v, self.astrom.em, self.astrom.bm1);
+        let mut cirs = [0.0; 3];
+        rxp(&self.astrom.bpn, &aberrated, &mut cirs);
+        let (cirs_right_ascension, cirs_declination) = c2s(&cirs);
+
+        self.observe_cirs(
+            cirs_right_ascension,
+            cirs_declination,
+            Some(Distance::AstronomicalUnits(distance)),
+        )
+    }
+
+    /// Observed position of a solar system body from its geocentric apparent place
+    /// => Right ascension and declination in radians, referred to the true equator and equinox of date
+    /// => Geocentric distance in AU
+    pub(crate) fn observe_geocentric(
+        &self,
+        right_ascension: f64,
+        declination: f64,
+        distance: f64,
+    ) -> Position {
+        let cirs_right_ascension = right_ascension + self.equation_of_origins;
+        let geocentric = sxp(
+            distance * ASTRONOMICAL_UNIT,
+            &s2c(cirs_right_ascension - self.astrom.eral, declination),
+        );
+        let topocentric = pmp(&geocentric, &self.observer_geocentric);
+        let (minus_hour_angle, topocentric_declination) = c2s(&topocentric);
+
+        self.observe_cirs(
+            self.astrom.eral + minus_hour_angle,
+            topocentric_declination,
+            Some(Distance::AstronomicalUnits(
+                pm(topocentric) / ASTRONOMICAL_UNIT,
+            )),
+        )
+    }
+
+    /// ICRS unit vector of an observed direction, azimuth and refracted altitude in radians
+    /// => The inverse of the observing pipeline for fixed objects, good to a few arcseconds
+    /// => Diurnal aberration and polar motion are ignored
+    pub(crate) fn horizontal_to_icrs(&self, azimuth: f64, altitude: f64) -> [f64; 3] {
+        // Undo refraction with the same model atioq applies, evaluated at the observed zenith distance
+        let zenith_distance = FRAC_PI_2 - altitude;
+        let tan_zenith_distance = zenith_distance.min(MAX_REFRACTION_ZENITH_DISTANCE).tan();
+        let geometric_altitude = FRAC_PI_2
+            - zenith_distance
+            - (self.astrom.refa + self.astrom.refb * tan_zenith_distance.powi(2))
+                * tan_zenith_distance;
+
+        let (sin_azimuth, cos_azimuth) = azimuth.sin_cos();
+        let (sin_altitude, cos_altitude) = geometric_altitude.sin_cos();
+        let (sin_latitude, cos_latitude) = (self.astrom.sphi, self.astrom.cphi);
+        let hour_angle = (-cos_altitude * sin_azimuth)
+            .atan2(cos_latitude * sin_altitude - sin_latitude * cos_altitude * cos_azimuth);
+        let declination = (sin_latitude * sin_altitude + cos_latitude * cos_altitude * cos_azimuth)
+            .clamp(-1.0, 1.0)
+            .asin();
+
+        self.cirs_to_icrs(self.astrom.eral - hour_angle, declination)
+    }
+
+    /// ICRS unit vector of a topocentric apparent right ascension and declination in radians
+    pub(crate) fn apparent_to_icrs(&self, right_ascension: f64, declination: f64) -> [f64; 3] {
+        self.cirs_to_icrs(right_ascension + self.equation_of_origins, declination)
+    }
+
+    fn cirs_to_icrs(&self, right_ascension: f64, declination: f64) -> [f64; 3] {
+        let mut astrom = self.astrom;
+        let (icrs_right_ascension, icrs_declination) =
+            aticq(right_ascension, declination, &mut astrom);
+        s2c(icrs_right_ascension, icrs_declination)
+    }
+
+    /// Observed position of an object from its topocentric CIRS coordinates in radians
+    pub(crate) fn observe_cirs(
+        &self,
+        right_ascension: f64,
+        declination: f64,
+        distance: Option<Distance>,
+    ) -> Position {
+        let (azimuth, zenith_distance, _, _, _) = atioq(right_ascension, declination, &self.astrom);
+
+        let mut unrefracted = self.astrom;
+        unrefracted.refa = 0.0;
+        unrefracted.refb = 0.0;
+        let (
+            _,
+            geometric_zenith_distance,
+            hour_angle,
+            topocentric_declination,
+            topocentric_right_ascension,
+        ) = atioq(right_ascension, declination, &unrefracted);
+
+        Position {
+            azimuth: azimuth.to_degrees(),
+            altitude: 90.0 - zenith_distance.to_degrees(),
+            geometric_altitude: 90.0 - geometric_zenith_distance.to_degrees(),
+            right_ascension: anp(topocentric_right_ascension - self.equation_of_origins)
+                .to_degrees(),
+            declination: topocentric_declination.to_degrees(),
+            hour_angle: hour_angle.to_degrees(),
+            distance,
+        }
+    }
+}
@@ -2,6 +2,7 @@ use crate::error::{CoreError, CoreResult};
 use sofars::ts;
 
 /// Naive date & time in UTC
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Time {
     pub year: i32,
     pub month: u32,
@@ -35,6 +36,49 @@ impl Time {
         }
     }
 
+    /// UTC quasi Julian Date as a single number
+    /// => Resolution of a few microseconds, plenty for event searches
+    pub fn get_julian_day(&self) -> CoreResult<f64> {
+        let (utc1, utc2) = self.get_double_julian()?;
+        Ok(utc1 + utc2)
+    }
+
+    /// Julian Ephemeris Day (TT) as a single number
+    /// => Only converts the time scale, so it is much cheaper than setting up `Astrometry`
+    pub fn get_julian_ephemeris_day(&self) -> CoreResult<f64> {
+        let (utc1, utc2) = self.get_double_julian()?;
+        let Ok((tai1, tai2)) = ts::utctai(utc1, utc2) else {
+            return Err(CoreError::TimeBadUnknown);
+        };
+        let Ok((tt1, tt2)) = ts::taitt(tai1, tai2) else {
+            return Err(CoreError::TimeBadUnknown);
+        };
+        Ok(tt1 + tt2)
+    }
+
+    pub fn from_double_julian(utc1: f64, utc2: f64) -> CoreResult<Self> {
+        match ts::d2dtf("UTC", 3, utc1, utc2) {
+            Ok((year, month, day, [hour, minute, second, millisecond])) => Ok(Self::from_utc(
+                year,
+                month as u32,
+                day as u32,
+                hour as u32,
+                minute as u32,
+                second as f64 + millisecond as f64 / 1000.0,
+            )),
+            Err(_) => Err(CoreError::TimeFromJulianDay),
+        }
+    }
+
+    pub fn from_julian_day(julian_day: f64) -> CoreResult<Self> {
+        Self::from_double_julian(julian_day, 0.0)
+    }
+
+    pub fn add_days(&self, days: f64) -> CoreResult<Self> {
+        let (utc1, utc2) = self.get_double_julian()?;
+        Self::from_double_julian(utc1, utc2 + days)
+    }
+
     pub fn from_utc(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: f64) -> Self {
         Self {
             year,
@@ -82,4 +126,19 @@ impl Time {
     pub fn now() -> Self {
         Self::from_datetime(chrono::Utc::now())
     }
+
+    #[cfg(feature = "chrono")]
+    pub fn to_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        let whole_seconds = self.second.trunc();
+        let nanosecond = ((self.second - whole_seconds) * 1_000_000_000.0).round() as u32;
+        // chrono represents a leap second as the 59th second with an overflowing nanosecond
+        let (second, nanosecond) = if whole_seconds >= 60.0 {
+            (59, nanosecond + 1_000_000_000)
+        } else {
+            (whole_seconds as u32, nanosecond)
+        };
+        chrono::NaiveDate::from_ymd_opt(self.year, self.month, self.day)?
+            .and_hms_nano_opt(self.hour, self.minute, second, nanosecond)
+            .map(|naive| naive.and_utc())
+    }
 }
@@ -0,0 +1,286 @@
+use crate::celestial_body::{vsop87_heliocentric_position, CelestialBody};
+use crate::error::CoreResult;
+use crate::math::angular_separation;
+use crate::position::astrometry::Astrometry;
+use crate::position::time::Time;
+use crate::position::Position;
+use sofars::vm::{c2s, pdp, pm, pmp, ppsp, s2c, sxp};
+use tracing::instrument;
+
+/// Extra radius around the query when comparing catalog places, in degrees
+/// => Covers proper motion since J2000.0, parallax and the approximations of the reverse transformation
+pub(crate) const CATALOG_MARGIN: f64 = 0.2;
+/// Extra radius around the query when comparing the coarse places of moving bodies, in degrees
+/// => Covers aberration, light time, refraction and the approximations of the reverse transformation
+const MOVING_MARGIN: f64 = 1.0;
+/// Time step the motion of solar system bodies is extrapolated over, in days
+const SOLAR_SYSTEM_STEP: f64 = 1.0 / 24.0;
+/// Time step the motion of satellites is extrapolated over, in days
+const SATELLITE_STEP: f64 = 1.0 / 1440.0;
+/// Equatorial radius of the Earth in AU
+/// => The largest shift between the geocentric and the topocentric place of a solar system body
+const EARTH_RADIUS: f64 = 6378.137 / 149_597_870.7;
+/// Upper bound of a satellite's acceleration in km/s², the gravity at the Earth's surface
+const MAX_SATELLITE_ACCELERATION: f64 = 0.0098;
+const SECONDS_PER_DAY: f64 = 86400.0;
+
+/// A direction on the observer's sky, angles in degrees
+#[derive(Debug, Clone, Copy)]
+pub enum SkyDirection {
+    /// Azimuth (0°=N, 90°=E) and altitude including refraction, as in `Position`
+    Horizontal { azimuth: f64, altitude: f64 },
+    /// Topocentric apparent right ascension and declination, as in `Position`
+    Equatorial {
+        right_ascension: f64,
+        declination: f64,
+    },
+}
+
+/// A body found close to a queried direction
+#[derive(Debug, Clone, Copy)]
+pub struct NearbyBody<'a> {
+    pub body: &'a CelestialBody,
+    pub position: Position,
+    /// Angular distance from the queried direction in degrees
+    pub separation: f64,
+    pub visual_magnitude: f64,
+}
+
+/// All bodies within `radius` degrees of a direction, closest first
+/// => Bodies at the same distance are ordered brightest first
+/// => Bodies fixed on the sky are compared by their catalog place first, so only the few close ones are placed exactly
+/// => Bodies whose position cannot be computed (e.g. decayed satellites) are left out
+#[instrument(skip_all, name = "skyseeker::query::bodies_near")]
+pub fn bodies_near<'a>(
+    bodies: impl IntoIterator<Item = &'a CelestialBody>,
+    direction: &SkyDirection,
+    radius: f64,
+    astrometry: &Astrometry,
+    time: &Time,
+) -> CoreResult<Vec<NearbyBody<'a>>> {
+    let icrs_direction = icrs_direction(direction, astrometry);
+    let min_catalog_cosine = (radius + CATALOG_MARGIN).min(180.0).to_radians().cos();
+
+    let mut nearby = Vec::new();
+    for body in bodies {
+        if let Some((right_ascension, declination)) = body.catalog_coordinates()
+            && pdp(&s2c(right_ascension, declination), &icrs_direction) < min_catalog_cosine
+        {
+            continue;
+        }
+
+        let Ok(position) = body.position_with(astrometry) else {
+            continue;
+        };
+        let separation = separation_from(direction, &position);
+        if separation <= radius {
+            nearby.push(NearbyBody {
+                body,
+                position,
+                separation,
+                visual_magnitude: body.visual_magnitude(time)?,
+            });
+        }
+    }
+
+    nearby.sort_by(|a, b| {
+        a.separation
+            .total_cmp(&b.separation)
+            .then(a.visual_magnitude.total_cmp(&b.visual_magnitude))
+    });
+    Ok(nearby)
+}
+
+/// Geocentric position and velocity of a moving body at the start of a time step
+#[derive(Debug)]
+struct Motion {
+    id: String,
+    position: [f64; 3],
+    /// Change of the position per day
+    velocity: [f64; 3],
+}
+
+impl Motion {
+    fn at(&self, days: f64) -> [f64; 3] {
+        ppsp(&self.position, days, &self.velocity)
+    }
+}
+
+/// Linear motion of the moving bodies over one time step, to rule most of them out cheaply
+/// => Solar system bodies in AU on the ICRS axes over TT steps, satellites in km in SGP4's TEME frame over UTC steps
+#[derive(Debug, Default)]
+struct Motions {
+    /// Julian day the step starts at, None until the first query
+    start: Option<f64>,
+    motions: Vec<Motion>,
+}
+
+impl Motions {
+    /// Days since the start of the step containing `julian_day`, set up anew when a query leaves the step
+    fn refresh(
+        &mut self,
+        julian_day: f64,
+        step: f64,
+        motions: impl FnOnce(f64) -> Vec<Motion>,
+    ) -> f64 {
+        let start = (julian_day / step).floor() * step;
+        if self.start != Some(start) {
+            self.motions = motions(start);
+            self.start = Some(start);
+        }
+        julian_day - start
+    }
+}
+
+/// Motions of the moving bodies, kept between queries since working them out costs as much as placing the bodies
+/// => Placing a moving body exactly is only worth it if its coarse place is close to the queried direction
+#[derive(Debug, Default)]
+pub(crate) struct MovingBodies {
+    solar_system: Motions,
+    satellites: Motions,
+}
+
+impl MovingBodies {
+    /// Ids of the bodies without a catalog place that may be within `radius` degrees of an ICRS direction
+    /// => `bodies` is only gone through when a query leaves the time step of the last one
+    pub(crate) fn candidates<'a>(
+        &mut self,
+        bodies: impl Iterator<Item = &'a CelestialBody> + Clone,
+        icrs_direction: &[f64; 3],
+        radius: f64,
+        astrometry: &Astrometry,
+    ) -> Vec<String> {
+        let mut candidates = Vec::new();
+
+        let days = self.solar_system.refresh(
+            astrometry.julian_ephemeris_day(),
+            SOLAR_SYSTEM_STEP,
+            |start| solar_system_motions(bodies.clone(), start),
+        );
+        for motion in &self.solar_system.motions {
+            let position = motion.at(days);
+            let parallax = (EARTH_RADIUS / pm(position)).min(1.0).asin().to_degrees();
+            if is_within(&position, icrs_direction, radius + parallax + MOVING_MARGIN) {
+                candidates.push(motion.id.clone());
+            }
+        }
+
+        // Satellites are compared topocentrically on the true equator of date, their parallax is too large to bound
+        let (icrs_right_ascension, icrs_declination) = c2s(icrs_direction);
+        let apparent =
+            astrometry.observe_catalog(icrs_right_ascension, icrs_declination, 0.0, 0.0, 0.0, 0.0);
+        let apparent_direction = s2c(
+            apparent.right_ascension.to_radians(),
+            apparent.declination.to_radians(),
+        );
+        let observer = astrometry
+            .observer_equatorial()
+            .map(|meters| meters / 1000.0);
+        let days = self
+            .satellites
+            .refresh(astrometry.julian_day(), SATELLITE_STEP, |start| {
+                satellite_motions(bodies, start)
+            });
+        let extrapolation_error =
+            MAX_SATELLITE_ACCELERATION * (days * SECONDS_PER_DAY).powi(2) / 2.0;
+        for motion in &self.satellites.motions {
+            let topocentric = pmp(&motion.at(days), &observer);
+            let error = (extrapolation_error / pm(topocentric))
+                .min(1.0)
+                .asin()
+                .to_degrees();
+            if is_within(
+                &topocentric,
+                &apparent_direction,
+                radius + error + MOVING_MARGIN,
+            ) {
+                candidates.push(motion.id.clone());
+            }
+        }
+
+        candidates
+    }
+}
+
+/// Geocentric motion of the solar system bodies from their places at both ends of the step
+/// => The Earth is taken from VSOP87, unlike the Earth ephemeris it covers any date
+fn solar_system_motions<'a>(
+    bodies: impl Iterator<Item = &'a CelestialBody>,
+    start: f64,
+) -> Vec<Motion> {
+    let end = start + SOLAR_SYSTEM_STEP;
+    let earth_at_start = vsop87_heliocentric_position(&astro::planet::Planet::Earth, start);
+    let earth_at_end = vsop87_heliocentric_position(&astro::planet::Planet::Earth, end);
+    bodies
+        .filter_map(|body| {
+            let position = body.geocentric_position(start, &earth_at_start)?;
+            let position_at_end = body.geocentric_position(end, &earth_at_end)?;
+            Some(Motion {
+                id: body.id().to_string(),
+                position,
+                velocity: sxp(1.0 / SOLAR_SYSTEM_STEP, &pmp(&position_at_end, &position)),
+            })
+        })
+        .collect()
+}
+
+/// Geocentric motion of the satellites at the start of the step, as SGP4 gives it
+/// => Satellites that cannot be propagated are left out, they cannot be placed either
+fn satellite_motions<'a>(
+    bodies: impl Iterator<Item = &'a CelestialBody>,
+    start: f64,
+) -> Vec<Motion> {
+    bodies
+        .filter_map(|body| {
+            let CelestialBody::Satellite(satellite) = body else {
+                return None;
+            };
+            let prediction = satellite.propagate(start).ok()?;
+            Some(Motion {
+                id: body.id().to_string(),
+                position: prediction.position,
+                velocity: sxp(SECONDS_PER_DAY, &prediction.velocity),
+            })
+        })
+        .collect()
+}
+
+/// Whether a vector is within `radius` degrees of a unit vector
+fn is_within(vector: &[f64; 3], direction: &[f64; 3], radius: f64) -> bool {
+    radius >= 180.0 || pdp(vector, direction) >= pm(*vector) * radius.to_radians().cos()
+}
+
+/// ICRS unit vector of a direction on the observer's sky
+pub(crate) fn icrs_direction(direction: &SkyDirection, astrometry: &Astrometry) -> [f64; 3] {
+    match *direction {
+        SkyDirection::Horizontal { azimuth, altitude } => {
+            astrometry.horizontal_to_icrs(azimuth.to_radians(), altitude.to_radians())
+        }
+        SkyDirection::Equatorial {
+            right_ascension,
+            declination,
+        } => astrometry.apparent_to_icrs(right_ascension.to_radians(), declination.to_radians()),
+    }
+}
+
+/// Angular distance of a position from a direction in degrees, in the direction's own coordinates
+fn separation_from(direction: &SkyDirection, position: &Position) -> f64 {
+    let (separation, _) = match *direction {
+        SkyDirection::Horizontal { azimuth, altitude } => angular_separation(
+            azimuth.to_radians(),
+            altitude.to_radians(),
+            position.azimuth.to_radians(),
+            position.altitude.to_radians(),
+        ),
+        SkyDirection::Equatorial {
+            right_ascension,
+            declination,
+        } => angular_separation(
+            right_ascension.to_radians(),
+            declination.to_radians(),
+            position.right_ascension.to_radians(),
+            position.declination.to_radians(),
+        ),
+    };
+    separation.to_degrees()
+}
@@ -0,0 +1,526 @@
+use crate::celestial_body::minor_body::MagnitudeParameters;
+use crate::celestial_body::{satellite, CelestialBody};
+use crate::constellation::{Constellation, CONSTELLATIONS};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use tracing::instrument;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Greek letters as full name, three letter abbreviation used by the BSC5 and letter
+const GREEK_LETTERS: [(&str, &str, char); 24] = [
+    ("alpha", "alp", 'α'),
+    ("beta", "bet", 'β'),
+    ("gamma", "gam", 'γ'),
+    ("delta", "del", 'δ'),
+    ("epsilon", "eps", 'ε'),
+    ("zeta", "zet", 'ζ'),
+    ("eta", "eta", 'η'),
+    ("theta", "the", 'θ'),
+    ("iota", "iot", 'ι'),
+    ("kappa", "kap", 'κ'),
+    ("lambda", "lam", 'λ'),
+    ("mu", "mu", 'μ'),
+    ("nu", "nu", 'ν'),
+    ("xi", "xi", 'ξ'),
+    ("omicron", "omi", 'ο'),
+    ("pi", "pi", 'π'),
+    ("rho", "rho", 'ρ'),
+    ("sigma", "sig", 'σ'),
+    ("tau", "tau", 'τ'),
+    ("upsilon", "ups", 'υ'),
+    ("phi", "phi", 'φ'),
+    ("chi", "chi", 'χ'),
+    ("psi", "psi", 'ψ'),
+    ("omega", "ome", 'ω'),
+];
+
+/// Field weights, a match in a more specific field ranks higher
+const NAME_WEIGHT: u8 = 5;
+const DESIGNATION_WEIGHT: u8 = 4;
+const CATALOG_NUMBER_WEIGHT: u8 = 3;
+const CONSTELLATION_WEIGHT: u8 = 1;
+
+/// Spelled out Greek letters and their abbreviations, keyed by normalized text
+static GREEK_FORMS: LazyLock<HashMap<String, &'static str>> = LazyLock::new(|| {
+    let mut forms = HashMap::new();
+    for (name, abbreviation, letter) in GREEK_LETTERS {
+        forms.insert(name.to_string(), name);
+        forms.insert(abbreviation.to_string(), name);
+        forms.insert(letter.to_string(), name);
+    }
+    // Final sigma and the alternative phi
+    forms.insert("ς".to_string(), "sigma");
+    forms.insert("ϕ".to_string(), "phi");
+    forms
+});
+
+/// Constellation abbreviations keyed by the normalized single word abbreviation, name or genitive
+static CONSTELLATION_FORMS: LazyLock<HashMap<String, String>> = LazyLock::new(|| {
+    let mut forms = HashMap::new();
+    for constellation in &CONSTELLATIONS {
+        let abbreviation = normalize(constellation.abbreviation);
+        for text in [
+            constellation.abbreviation,
+            constellation.name,
+            constellation.genitive,
+        ] {
+            let words = words(&normalize(text));
+            if let [word] = words.as_slice() {
+                forms.insert(word.clone(), abbreviation.clone());
+            }
+        }
+    }
+    forms
+});
+
+/// Multi word constellation names and genitives, e.g. ["ursae", "majoris"] => "uma"
+static CONSTELLATION_PHRASES: LazyLock<Vec<(Vec<String>, String)>> = LazyLock::new(|| {
+    let mut phrases = Vec::new();
+    for constellation in &CONSTELLATIONS {
+        for text in [constellation.name, constellation.genitive] {
+            let words = words(&normalize(text));
+            if words.len() > 1 {
+                phrases.push((words, normalize(constellation.abbreviation)));
+            }
+        }
+    }
+    phrases
+});
+
+/// Text index over the names and designations of bodies
+/// => Case- and accent-insensitive, Greek letters can be given as letter, abbreviation or spelled out
+#[derive(Debug, Default)]
+pub struct SearchIndex {
+    entries_by_id: HashMap<String, Entry>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    fields: Vec<Field>,
+    /// Rough brightness used to order equally good matches, lower is more prominent
+    prominence: f64,
+}
+
+#[derive(Debug)]
+struct Field {
+    text: String,
+    tokens: Vec<Token>,
+    weight: u8,
+}
+
+/// One word or number of a normalized text
+#[derive(Debug)]
+struct Token {
+    text: String,
+    /// Equivalent spellings, e.g. "alp" => ["alp", "alpha"], "bootis" => ["bootis", "boo"]
+    forms: Vec<String>,
+}
+
+/// A body found by a text search
+#[derive(Debug, Clone)]
+pub struct SearchMatch<'a> {
+    pub body: &'a CelestialBody,
+    /// The name or designation that matched, as stored in the catalog
+    pub matched_text: String,
+}
+
+/// How well a query matches one field, compared field by field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Score {
+    /// Every query word matches a whole word and no word of the field is left over
+    whole_field: bool,
+    /// Every query word matches a whole word, not only its beginning
+    all_exact: bool,
+    weight: u8,
+    exact_words: usize,
+}
+
+impl SearchIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a body, replacing any body with the same id
+    pub fn insert(&mut self, body: &CelestialBody) {
+        let fields = search_fields(body)
+            .into_iter()
+            .filter(|(text, _)| !text.trim().is_empty())
+            .map(|(text, weight)| Field {
+                tokens: tokenize(&text),
+                text,
+                weight,
+            })
+            .collect();
+        self.entries_by_id.insert(
+            body.id().to_string(),
+            Entry {
+                fields,
+                prominence: prominence(body),
+            },
+        );
+    }
+
+    pub fn remove(&mut self, body_id: impl AsRef<str>) {
+        self.entries_by_id.remove(body_id.as_ref());
+    }
+
+    /// Ids of the bodies matching a query, best match first, at most `limit` of them
+    /// => Every word of the query has to match the beginning of a word of one field, in the same order
+    /// => Whole word matches rank above prefix matches, names above designations above constellations
+    /// => Equally good matches are ordered brightest first
+    #[instrument(skip_all, name = "skyseeker::search")]
+    pub fn search(&self, query: &str, limit: usize) -> Vec<(&str, &str)> {
+        let query = tokenize(query);
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let mut matches: Vec<(Score, f64, &str, &str)> = self
+            .entries_by_id
+            .iter()
+            .filter_map(|(id, entry)| {
+                entry
+                    .fields
+                    .iter()
+                    .filter_map(|field| Some((score(&query, field)?, field.text.as_str())))
+                    // The first field wins ties, so the id is reported over its repetitions
+                    .reduce(|best, next| if next.0 > best.0 { next } else { best })
+                    .map(|(score, text)| (score, entry.prominence, id.as_str(), text))
+            })
+            .collect();
+
+        matches.sort_by(|a, b| {
+            b.0.cmp(&a.0)
+                .then(a.1.total_cmp(&b.1))
+                .then_with(|| a.2.cmp(b.2))
+        });
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|(_, _, id, text)| (id, text))
+            .collect()
+    }
+}
+
+/// Matches the query words in order against the words of a field
+fn score(query: &[Token], field: &Field) -> Option<Score> {
+    let mut exact_words = 0;
+    let mut next = 0;
+    for query_token in query {
+        let (offset, exact) =
+            field.tokens[next..]
+                .iter()
+                .enumerate()
+                .find_map(|(offset, token)| {
+                    if token
+                        .forms
+                        .iter()
+                        .any(|form| query_token.forms.contains(form))
+                    {
+                        Some((offset, true))
+                    } else if token.text.starts_with(&query_token.text) {
+                        Some((offset, false))
+                    } else {
+                        None
+                    }
+                })?;
+        next += offset + 1;
+        exact_words += usize::from(exact);
+    }
+
+    let all_exact = exact_words == query.len();
+    Some(Score {
+        whole_field: all_exact && query.len() == field.tokens.len(),
+        all_exact,
+        weight: field.weight,
+        exact_words,
+    })
+}
+
+/// Searchable names and designations of a body with their weights
+fn search_fields(body: &CelestialBody) -> Vec<(String, u8)> {
+    let mut fields = vec![(body.id().to_string(), DESIGNATION_WEIGHT)];
+    let mut constellation = None;
+    match body {
+        CelestialBody::Star(star) => {
+            fields.extend(star.common_name.clone().map(|name| (name, NAME_WEIGHT)));
+            fields.extend(star.name.clone().map(|name| (name, DESIGNATION_WEIGHT)));
+            fields.extend(
+                star.bayer_full
+                    .clone()
+                    .map(|bayer| (bayer, DESIGNATION_WEIGHT)),
+            );
+            fields.extend(star.hr.map(|hr| (hr.to_string(), CATALOG_NUMBER_WEIGHT)));
+            constellation = star.constellation.as_deref();
+        }
+        CelestialBody::DeepSky(deep_sky) => {
+            fields.extend(
+                deep_sky
+                    .common_names
+                    .iter()
+                    .map(|name| (name.clone(), NAME_WEIGHT)),
+            );
+            fields.extend(deep_sky.messier_id().map(|id| (id, DESIGNATION_WEIGHT)));
+            constellation = deep_sky.constellation.as_deref();
+        }
+        CelestialBody::MinorBody(minor_body) => {
+            fields.extend(minor_body.name.clone().map(|name| (name, NAME_WEIGHT)));
+        }
+        CelestialBody::Satellite(satellite) => {
+            fields.extend(satellite.name.clone().map(|name| (name, NAME_WEIGHT)));
+            fields.push((
+                format!("NORAD {}", satellite.norad_id),
+                CATALOG_NUMBER_WEIGHT,
+            ));
+        }
+        CelestialBody::Planet(_) | CelestialBody::Moon | CelestialBody::Sun => {
+            fields[0].1 = NAME_WEIGHT;
+        }
+    }
+
+    if let Some(constellation) = constellation {
+        fields.push((constellation.to_string(), CONSTELLATION_WEIGHT));
+        if let Some(Constellation { name, .. }) = Constellation::from_abbreviation(constellation) {
+            fields.push((name.to_string(), CONSTELLATION_WEIGHT));
+        }
+    }
+    fields
+}
+
+/// Magnitude-like value used to order equally good matches
+/// => The Sun, Moon and planets come first, minor bodies by their absolute magnitude
+fn prominence(body: &CelestialBody) -> f64 {
+    match body {
+        CelestialBody::Star(star) => star.visual_magnitude,
+        CelestialBody::DeepSky(deep_sky) => deep_sky.magnitude(),
+        CelestialBody::MinorBody(minor_body) => match minor_body.magnitude {
+            MagnitudeParameters::Asteroid {
+                absolute_magnitude, ..
+            }
+            | MagnitudeParameters::Comet {
+                absolute_magnitude, ..
+            } => absolute_magnitude,
+        },
+        CelestialBody::Satellite(_) => satellite::NOMINAL_MAGNITUDE,
+        CelestialBody::Planet(_) | CelestialBody::Moon | CelestialBody::Sun => f64::NEG_INFINITY,
+    }
+}
+
+/// Lowercase without accents, superscripts and other compatibility characters folded to plain ones
+fn normalize(text: &str) -> String {
+    text.nfkd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Splits normalized text into words, also between letters and digits ("16alp" => "16", "alp")
+fn words(text: &str) -> Vec<String> {
+    #[derive(PartialEq)]
+    enum Class {
+        Digit,
+        Latin,
+        Greek,
+    }
+    let class = |c: char| {
+        if c.is_ascii_digit() {
+            Some(Class::Digit)
+        } else if ('\u{0370}'..='\u{03ff}').contains(&c) {
+            Some(Class::Greek)
+        } else if c.is_alphanumeric() {
+            Some(Class::Latin)
+        } else {
+            None
+        }
+    };
+
+    let mut words: Vec<String> = Vec::new();
+    let mut previous = None;
+    for c in text.chars() {
+        let current = class(c);
+        match &current {
+            Some(current_class) if previous.as_ref() == Some(current_class) => {
+                if let Some(word) = words.last_mut() {
+                    word.push(c);
+                }
+            }
+            Some(_) => words.push(c.to_string()),
+            None => {}
+        }
+        previous = current;
+    }
+    words
+}
+
+fn tokenize(text: &str) -> Vec<Token> {
+    let mut words = words(&normalize(text));
+
+    // Collapse multi word constellation names into their abbreviation
+    for (phrase, abbreviation) in CONSTELLATION_PHRASES.iter() {
+        while let Some(start) = words
+            .windows(phrase.len())
+            .position(|window| window == phrase.as_slice())
+        {
+            words.splice(start..start + phrase.len(), [abbreviation.clone()]);
+        }
+    }
+
+    words
+        .into_iter()
+        .map(|word| {
+            let mut forms = vec![word.clone()];
+            if word.chars().all(|c| c.is_ascii_digit()) {
+                let trimmed = word.trim_start_matches('0');
+                forms.push(if trimmed.is_empty() { "0" } else { trimmed }.to_string());
+            }
+            if let Some(greek) = GREEK_FORMS.get(&word) {
+                forms.push(greek.to_string());
+            }
+            if let Some(abbreviation) = CONSTELLATION_FORMS.get(&word) {
+                forms.push(abbreviation.clone());
+            }
+            Token { text: word, forms }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SearchIndex;
+    use crate::celestial_body::star::Star;
+    use crate::celestial_body::CelestialBody;
+    use std::sync::Arc;
+
+    fn star(
+        hr: u16,
+        name: Option<&str>,
+        common_name: Option<&str>,
+        bayer_full: Option<&str>,
+        visual_magnitude: f64,
+    ) -> CelestialBody {
+        CelestialBody::Star(Arc::new(Star {
+            id: format!("HR {}", hr),
+            hr: Some(hr),
+            name: name.map(str::to_string),
+            common_name: common_name.map(str::to_string),
+            bayer: None,
+            bayer_full: bayer_full.map(str::to_string),
+            constellation: bayer_full
+                .and_then(|bayer| bayer.split_whitespace().last())
+                .map(str::to_string),
+            notes: Vec::new(),
+            right_ascension: 0.0,
+            declination: 0.0,
+            proper_motion_right_ascension: 0.0,
+            proper_motion_declination: 0.0,
+            parallax: 0.0,
+            radial_velocity: 0.0,
+            visual_magnitude,
+            b_v_color: None,
+        }))
+    }
+
+    fn index(bodies: &[CelestialBody]) -> SearchIndex {
+        let mut index = SearchIndex::new();
+        for body in bodies {
+            index.insert(body);
+        }
+        index
+    }
+
+    fn bright_stars() -> SearchIndex {
+        index(&[
+            star(
+                5340,
+                Some("16Alp Boo"),
+                Some("Arcturus"),
+                Some("Alp Boo"),
+                -0.04,
+            ),
+            star(
+                5235,
+                Some("8Eta Boo"),
+                Some("Muphrid"),
+                Some("Eta Boo"),
+                2.68,
+            ),
+            star(7001, Some("3Alp Lyr"), Some("Vega"), Some("Alp Lyr"), 0.03),
+        ])
+    }
+
+    fn ids(index: &SearchIndex, query: &str) -> Vec<String> {
+        index
+            .search(query, 10)
+            .into_iter()
+            .map(|(id, _)| id.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn spelled_out_greek_letter_finds_arcturus() {
+        let index = bright_stars();
+        let matches = index.search("alpha Boo", 10);
+        assert_eq!(matches, vec![("HR 5340", "Alp Boo")]);
+    }
+
+    #[test]
+    fn greek_letter_and_constellation_spellings_are_equivalent() {
+        let index = bright_stars();
+        for query in [
+            "alpha boo",
+            "α Boötis",
+            "Alpha Bootis",
+            "ALP BOO",
+            "alp Boötes",
+        ] {
+            assert_eq!(
+                index.search(query, 1),
+                vec![("HR 5340", "Alp Boo")],
+                "query {query:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn whole_field_ranks_above_whole_words_in_a_longer_field() {
+        let index = index(&[
+            star(1, None, Some("Mira Ceti"), None, 1.0),
+            star(2, None, Some("Mira"), None, 5.0),
+        ]);
+        assert_eq!(ids(&index, "mira"), ["HR 2", "HR 1"]);
+    }
+
+    #[test]
+    fn exact_words_rank_above_prefixes() {
+        let index = index(&[
+            star(1, None, Some("Vegas"), None, -1.0),
+            star(2, None, Some("Vega"), None, 0.03),
+        ]);
+        assert_eq!(ids(&index, "vega"), ["HR 2", "HR 1"]);
+        // Only prefixes match, so brightness decides
+        assert_eq!(ids(&index, "veg"), ["HR 1", "HR 2"]);
+    }
+
+    #[test]
+    fn names_rank_above_designations() {
+        let index = index(&[
+            star(1, Some("Sadr"), None, None, 1.0),
+            star(2, None, Some("Sadr"), None, 2.0),
+        ]);
+        assert_eq!(ids(&index, "sadr"), ["HR 2", "HR 1"]);
+    }
+
+    #[test]
+    fn equally_good_matches_are_ordered_brightest_first() {
+        let index = bright_stars();
+        assert_eq!(ids(&index, "alpha"), ["HR 5340", "HR 7001"]);
+        assert_eq!(ids(&index, "boo"), ["HR 5340", "HR 5235"]);
+    }
+
+    #[test]
+    fn query_words_match_in_order() {
+        let index = bright_stars();
+        assert!(index.search("boo alpha", 10).is_empty());
+    }
+}
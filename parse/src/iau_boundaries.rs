@@ -0,0 +1,33 @@
+use anyhow::{bail, Context};
+use skyseeker_core::constellation::{BoundaryZone, Constellation, ConstellationBoundaries};
+
+/// Parses the constellation boundaries of CDS catalog VI/42 (Roman 1987)
+/// => One zone per line: lower and upper right ascension in hours, lower declination in degrees, abbreviation
+pub fn parse(data: String) -> anyhow::Result<ConstellationBoundaries> {
+    let mut zones = Vec::new();
+    for line in data.lines().filter(|line| !line.trim().is_empty()) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [right_ascension_low, right_ascension_high, declination_low, abbreviation] = fields[..]
+        else {
+            bail!("expected 4 fields in boundary zone '{}'", line);
+        };
+        let constellation = Constellation::from_abbreviation(abbreviation)
+            .with_context(|| format!("unknown constellation '{}'", abbreviation))?;
+        zones.push(BoundaryZone {
+            right_ascension_low: hours_to_degrees(right_ascension_low)?,
+            right_ascension_high: hours_to_degrees(right_ascension_high)?,
+            declination_low: declination_low
+                .parse()
+                .with_context(|| format!("bad declination '{}'", declination_low))?,
+            abbreviation: constellation.abbreviation.to_string(),
+        });
+    }
+    Ok(ConstellationBoundaries::new(zones))
+}
+
+fn hours_to_degrees(hours: &str) -> anyhow::Result<f64> {
+    let hours: f64 = hours
+        .parse()
+        .with_context(|| format!("bad right ascension '{}'", hours))?;
+    Ok(hours * 15.0)
+}
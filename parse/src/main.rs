@@ -2,6 +2,15 @@ use skyseeker_core::codec::encode;
 use std::path::PathBuf;
 
 mod bsc5;
+mod iau_boundaries;
+mod mpc;
+mod openngc;
+mod stellarium;
+mod tle;
+
+/// Asteroids fainter than this absolute magnitude are left out
+/// => Keeps the few thousand largest ones, which includes everything reaching binocular range
+const MAX_ASTEROID_ABSOLUTE_MAGNITUDE: f64 = 11.0;
 
 fn main() {
     let data_dir = PathBuf::from("./../data");
@@ -12,4 +21,50 @@ fn main() {
     let stars = bsc5::parse(bsc5_data).unwrap();
     let star_data = encode(&stars).unwrap();
     std::fs::write(bsc5_output, star_data).unwrap();
+
+    let figures_input = data_dir.join("constellationship.fab");
+    let hipparcos_input = data_dir.join("hip_main.dat");
+    let figures_output = data_dir.join("stellarium-constellation-figures.bin");
+
+    let figures_data = std::fs::read_to_string(figures_input).unwrap();
+    let hipparcos_data = std::fs::read_to_string(hipparcos_input).unwrap();
+    let figures = stellarium::parse_figures(figures_data, hipparcos_data, &stars).unwrap();
+    let figure_data = encode(&figures).unwrap();
+    std::fs::write(figures_output, figure_data).unwrap();
+
+    let openngc_input = data_dir.join("NGC.csv");
+    let openngc_output = data_dir.join("openngc-deep-sky.bin");
+
+    let openngc_data = std::fs::read_to_string(openngc_input).unwrap();
+    let deep_sky_objects = openngc::parse(openngc_data).unwrap();
+    let deep_sky_data = encode(&deep_sky_objects).unwrap();
+    std::fs::write(openngc_output, deep_sky_data).unwrap();
+
+    let asteroid_input = data_dir.join("MPCORB.DAT");
+    let comet_input = data_dir.join("CometEls.txt");
+    let minor_body_output = data_dir.join("mpc-minor-bodies.bin");
+
+    let asteroid_data = std::fs::read_to_string(asteroid_input).unwrap();
+    let comet_data = std::fs::read_to_string(comet_input).unwrap();
+    let mut minor_bodies =
+        mpc::parse_asteroids(asteroid_data, MAX_ASTEROID_ABSOLUTE_MAGNITUDE).unwrap();
+    minor_bodies.extend(mpc::parse_comets(comet_data).unwrap());
+    let minor_body_data = encode(&minor_bodies).unwrap();
+    std::fs::write(minor_body_output, minor_body_data).unwrap();
+
+    let tle_input = data_dir.join("visual.tle");
+    let tle_output = data_dir.join("tle-satellites.bin");
+
+    let tle_data = std::fs::read_to_string(tle_input).unwrap();
+    let satellites = tle::parse(tle_data).unwrap();
+    let satellite_data = encode(&satellites).unwrap();
+    std::fs::write(tle_output, satellite_data).unwrap();
+
+    let boundaries_input = data_dir.join("constellation-boundaries.dat");
+    let boundaries_output = data_dir.join("iau-constellation-boundaries.bin");
+
+    let boundaries_data = std::fs::read_to_string(boundaries_input).unwrap();
+    let boundaries = iau_boundaries::parse(boundaries_data).unwrap();
+    let boundary_data = encode(&boundaries).unwrap();
+    std::fs::write(boundaries_output, boundary_data).unwrap();
 }
@@ -0,0 +1,225 @@
+use anyhow::{bail, Context};
+use skyseeker_core::celestial_body::minor_body::{MagnitudeParameters, MinorBody, OrbitalElements};
+use skyseeker_core::celestial_body::CelestialBody;
+use std::sync::Arc;
+
+/// Slope parameter assumed for asteroids without one
+const DEFAULT_SLOPE: f64 = 0.15;
+
+/// Parses the MPCORB one-line format for asteroids (MPCORB.DAT, NEA.txt, ...)
+/// => Only asteroids with an absolute magnitude up to `max_absolute_magnitude` are kept
+/// => The full MPCORB.DAT has over a million entries, most of them far too faint to ever see
+pub fn parse_asteroids(
+    data: String,
+    max_absolute_magnitude: f64,
+) -> anyhow::Result<Vec<CelestialBody>> {
+    let asteroids = orbit_lines(&data)
+        .filter_map(|line| {
+            (|| -> anyhow::Result<Option<CelestialBody>> {
+                let absolute_magnitude = column(line, 8, 13)
+                    .parse::<f64>()
+                    .context("failed to parse absolute magnitude")?;
+                if absolute_magnitude > max_absolute_magnitude {
+                    return Ok(None);
+                }
+
+                let slope = match column(line, 14, 19) {
+                    "" => DEFAULT_SLOPE,
+                    slope => slope.parse::<f64>().context("failed to parse slope")?,
+                };
+
+                let epoch =
+                    parse_packed_date(column(line, 20, 25)).context("failed to parse epoch")?;
+                let orbit = OrbitalElements::from_mean_anomaly(
+                    epoch,
+                    parse_degrees(line, 26, 35).context("failed to parse mean anomaly")?,
+                    column(line, 92, 103)
+                        .parse::<f64>()
+                        .context("failed to parse semi-major axis")?,
+                    column(line, 70, 79)
+                        .parse::<f64>()
+                        .context("failed to parse eccentricity")?,
+                    parse_degrees(line, 37, 46)
+                        .context("failed to parse argument of perihelion")?,
+                    parse_degrees(line, 48, 57).context("failed to parse ascending node")?,
+                    parse_degrees(line, 59, 68).context("failed to parse inclination")?,
+                );
+
+                let designation = match column(line, 166, 194) {
+                    "" => column(line, 0, 7),
+                    readable => readable,
+                };
+                // "(1) Ceres" => "Ceres", provisional designations like "2024 AB1" have no name
+                let name = designation
+                    .strip_prefix('(')
+                    .and_then(|rest| rest.split_once(") "))
+                    .map(|(_, name)| name.to_string());
+
+                let minor_body = MinorBody {
+                    id: designation.to_string(),
+                    name,
+                    orbit,
+                    magnitude: MagnitudeParameters::Asteroid {
+                        absolute_magnitude,
+                        slope,
+                    },
+                };
+
+                Ok(Some(CelestialBody::MinorBody(Arc::new(minor_body))))
+            })()
+            .inspect_err(|e| println!("Skipping entry '{}' in MPCORB: {}", column(line, 0, 7), e))
+            .ok()
+            .flatten()
+        })
+        .collect();
+
+    Ok(asteroids)
+}
+
+/// Parses the MPC one-line format for comets (CometEls.txt)
+pub fn parse_comets(data: String) -> anyhow::Result<Vec<CelestialBody>> {
+    let comets = orbit_lines(&data)
+        .filter_map(|line| {
+            (|| -> anyhow::Result<CelestialBody> {
+                let perihelion_time =
+                    parse_perihelion_time(line).context("failed to parse time of perihelion")?;
+
+                let epoch = match column(line, 81, 89) {
+                    // Comets on long period orbits often come without an osculation epoch
+                    "" => perihelion_time,
+                    epoch => parse_calendar_date(
+                        epoch.get(0..4).context("missing year")?,
+                        epoch.get(4..6).context("missing month")?,
+                        epoch.get(6..8).context("missing day")?,
+                    )
+                    .context("failed to parse epoch")?,
+                };
+
+                let orbit = OrbitalElements {
+                    epoch,
+                    perihelion_time,
+                    perihelion_distance: column(line, 30, 39)
+                        .parse::<f64>()
+                        .context("failed to parse perihelion distance")?,
+                    eccentricity: column(line, 41, 49)
+                        .parse::<f64>()
+                        .context("failed to parse eccentricity")?,
+                    argument_of_perihelion: parse_degrees(line, 51, 59)
+                        .context("failed to parse argument of perihelion")?,
+                    ascending_node: parse_degrees(line, 61, 69)
+                        .context("failed to parse ascending node")?,
+                    inclination: parse_degrees(line, 71, 79)
+                        .context("failed to parse inclination")?,
+                };
+
+                let magnitude = MagnitudeParameters::Comet {
+                    absolute_magnitude: column(line, 91, 95)
+                        .parse::<f64>()
+                        .context("failed to parse absolute magnitude")?,
+                    slope: column(line, 96, 100)
+                        .parse::<f64>()
+                        .context("failed to parse slope")?,
+                };
+
+                let designation = column(line, 102, 158);
+                if designation.is_empty() {
+                    bail!("missing designation");
+                }
+
+                let minor_body = MinorBody {
+                    id: designation.to_string(),
+                    name: parse_comet_name(designation),
+                    orbit,
+                    magnitude,
+                };
+
+                Ok(CelestialBody::MinorBody(Arc::new(minor_body)))
+            })()
+            .inspect_err(|e| {
+                println!(
+                    "Skipping entry '{}' in CometEls: {}",
+                    column(line, 0, 12),
+                    e
+                )
+            })
+            .ok()
+        })
+        .collect();
+
+    Ok(comets)
+}
+
+/// Non-empty lines after the header, which MPCORB.DAT ends with a line of dashes
+fn orbit_lines(data: &str) -> impl Iterator<Item = &str> {
+    let body = match data.find("\n-----") {
+        Some(index) => data[index + 1..]
+            .split_once('\n')
+            .map_or("", |(_, rest)| rest),
+        None => data,
+    };
+    body.lines().filter(|line| !line.trim().is_empty())
+}
+
+/// Trimmed content of the 0-based, end-exclusive column range, empty if the line is too short
+fn column(line: &str, start: usize, end: usize) -> &str {
+    line.get(start..end.min(line.len()))
+        .unwrap_or_default()
+        .trim()
+}
+
+fn parse_degrees(line: &str, start: usize, end: usize) -> anyhow::Result<f64> {
+    Ok(column(line, start, end).parse::<f64>()?.to_radians())
+}
+
+/// "C/2023 A3 (Tsuchinshan-ATLAS)" => "Tsuchinshan-ATLAS", "1P/Halley" => "Halley"
+fn parse_comet_name(designation: &str) -> Option<String> {
+    if let Some((_, rest)) = designation.split_once('(') {
+        return rest.strip_suffix(')').map(|name| name.to_string());
+    }
+    let (number, name) = designation.split_once('/')?;
+    number
+        .trim_end_matches(['P', 'D', 'I'])
+        .chars()
+        .all(|c| c.is_ascii_digit())
+        .then(|| name.to_string())
+}
+
+/// Julian Ephemeris Day of the perihelion passage, given as year, month and fractional day (TT)
+fn parse_perihelion_time(line: &str) -> anyhow::Result<f64> {
+    let day = column(line, 22, 29).parse::<f64>()?;
+    let midnight = parse_calendar_date(column(line, 14, 18), column(line, 19, 21), "1")?;
+    Ok(midnight + day - 1.0)
+}
+
+fn parse_calendar_date(year: &str, month: &str, day: &str) -> anyhow::Result<f64> {
+    let year = year.trim().parse::<i32>()?;
+    let month = month.trim().parse::<i32>()?;
+    let day = day.trim().parse::<i32>()?;
+    match sofars::cal::cal2jd(year, month, day) {
+        Ok((mjd_zero, mjd)) => Ok(mjd_zero + mjd),
+        Err(_) => bail!("invalid date {}-{}-{}", year, month, day),
+    }
+}
+
+/// MPC packed date, e.g. "K24AH" => 2024-10-17 => Julian Day at 0h TT
+/// => Century I/J/K = 18/19/20, month and day 1-9 then A = 10, B = 11, ...
+fn parse_packed_date(packed: &str) -> anyhow::Result<f64> {
+    let characters: Vec<char> = packed.chars().collect();
+    let [century, decade, year, month, day] = characters[..] else {
+        bail!("expected 5 characters, got '{}'", packed);
+    };
+
+    let century = match century {
+        'I' => 18,
+        'J' => 19,
+        'K' => 20,
+        _ => bail!("invalid century '{}'", century),
+    };
+    let year = century * 100
+        + decade.to_digit(10).context("invalid year")? * 10
+        + year.to_digit(10).context("invalid year")?;
+    let month = month.to_digit(36).context("invalid month")?;
+    let day = day.to_digit(36).context("invalid day")?;
+
+    parse_calendar_date(&year.to_string(), &month.to_string(), &day.to_string())
+}
@@ -0,0 +1,191 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use skyseeker_core::celestial_body::deep_sky::{DeepSkyObject, DeepSkyObjectType};
+use skyseeker_core::celestial_body::CelestialBody;
+use skyseeker_core::math::{angle_format_to_radians, time_format_to_radians};
+use std::sync::Arc;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenNGCEntry {
+    /// Catalog name with zero padded number, e.g. "NGC0224" or "IC0434"
+    #[serde(alias = "Name")]
+    pub name: String,
+    /// Object type code, e.g. "G", "OCl" or "PN"
+    #[serde(alias = "Type")]
+    pub object_type: String,
+    /// Equinox J2000 right ascension, "HH:MM:SS.ss"
+    #[serde(alias = "RA")]
+    pub right_ascension: Option<String>,
+    /// Equinox J2000 declination, "+DD:MM:SS.s"
+    #[serde(alias = "Dec")]
+    pub declination: Option<String>,
+    #[serde(alias = "Const")]
+    pub constellation: Option<String>,
+    /// Major axis in arcminutes
+    #[serde(alias = "MajAx")]
+    pub major_axis: Option<String>,
+    /// Minor axis in arcminutes
+    #[serde(alias = "MinAx")]
+    pub minor_axis: Option<String>,
+    /// Position angle of the major axis in degrees, north through east
+    #[serde(alias = "PosAng")]
+    pub position_angle: Option<String>,
+    /// Blue magnitude in mag
+    #[serde(alias = "B-Mag")]
+    pub blue_magnitude: Option<String>,
+    /// Visual magnitude in mag
+    #[serde(alias = "V-Mag")]
+    pub visual_magnitude: Option<String>,
+    /// Mean surface brightness in mag/arcsec²
+    #[serde(alias = "SurfBr")]
+    pub surface_brightness: Option<String>,
+    /// Messier number, zero padded
+    #[serde(alias = "M")]
+    pub messier: Option<String>,
+    /// Comma separated list of common names
+    #[serde(alias = "Common names")]
+    pub common_names: Option<String>,
+}
+
+/// Parses the semicolon separated OpenNGC database (NGC.csv, addendum.csv)
+pub fn parse(data: String) -> anyhow::Result<Vec<CelestialBody>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(b';')
+        .from_reader(data.as_bytes());
+
+    let entries = reader
+        .deserialize::<OpenNGCEntry>()
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to deserialize OpenNGC data")?;
+
+    let objects = entries
+        .into_iter()
+        // Duplicates point to another entry, non-existent objects have no position
+        .filter(|entry| entry.object_type != "Dup" && entry.object_type != "NonEx")
+        .filter_map(|entry| {
+            (|| -> anyhow::Result<CelestialBody> {
+                let right_ascension = parse_right_ascension(
+                    entry
+                        .right_ascension
+                        .as_deref()
+                        .context("missing right ascension")?,
+                )
+                .context("failed to parse right ascension")?;
+
+                let declination = parse_declination(
+                    entry
+                        .declination
+                        .as_deref()
+                        .context("missing declination")?,
+                )
+                .context("failed to parse declination")?;
+
+                let messier = entry
+                    .messier
+                    .as_deref()
+                    .map(|messier| messier.parse::<u8>())
+                    .transpose()
+                    .context("failed to parse Messier number")?;
+
+                let major_axis =
+                    parse_optional(&entry.major_axis).context("failed to parse major axis")?;
+                let minor_axis = parse_optional(&entry.minor_axis)
+                    .context("failed to parse minor axis")?
+                    .or(major_axis);
+
+                let deep_sky_object = DeepSkyObject {
+                    id: parse_designation(&entry.name),
+                    messier,
+                    common_names: entry
+                        .common_names
+                        .iter()
+                        .flat_map(|names| names.split(','))
+                        .map(|name| name.trim().to_string())
+                        .filter(|name| !name.is_empty())
+                        .collect(),
+                    object_type: parse_object_type(&entry.object_type),
+                    constellation: entry.constellation,
+                    right_ascension,
+                    declination,
+                    major_axis,
+                    minor_axis,
+                    position_angle: parse_optional(&entry.position_angle)
+                        .context("failed to parse position angle")?,
+                    visual_magnitude: parse_optional(&entry.visual_magnitude)
+                        .context("failed to parse visual magnitude")?,
+                    blue_magnitude: parse_optional(&entry.blue_magnitude)
+                        .context("failed to parse blue magnitude")?,
+                    surface_brightness: parse_optional(&entry.surface_brightness)
+                        .context("failed to parse surface brightness")?,
+                };
+
+                Ok(CelestialBody::DeepSky(Arc::new(deep_sky_object)))
+            })()
+            .inspect_err(|e| println!("Skipping entry '{}' in OpenNGC: {}", entry.name, e))
+            .ok()
+        })
+        .collect();
+
+    Ok(objects)
+}
+
+/// "NGC0224" => "NGC 224", "IC0434" => "IC 434", "NGC5194A" => "NGC 5194A"
+fn parse_designation(name: &str) -> String {
+    let number_start = name.find(|c: char| c.is_ascii_digit());
+    match number_start {
+        Some(index) => {
+            let (catalog, number) = name.split_at(index);
+            let number = number.trim_start_matches('0');
+            format!("{} {}", catalog, number)
+        }
+        None => name.to_string(),
+    }
+}
+
+fn parse_object_type(code: &str) -> DeepSkyObjectType {
+    match code {
+        "*" => DeepSkyObjectType::Star,
+        "**" => DeepSkyObjectType::DoubleStar,
+        "*Ass" => DeepSkyObjectType::Association,
+        "OCl" => DeepSkyObjectType::OpenCluster,
+        "GCl" => DeepSkyObjectType::GlobularCluster,
+        "Cl+N" => DeepSkyObjectType::ClusterWithNebula,
+        "G" => DeepSkyObjectType::Galaxy,
+        "GPair" => DeepSkyObjectType::GalaxyPair,
+        "GTrpl" => DeepSkyObjectType::GalaxyTriplet,
+        "GGroup" => DeepSkyObjectType::GalaxyGroup,
+        "PN" => DeepSkyObjectType::PlanetaryNebula,
+        "HII" => DeepSkyObjectType::HIIRegion,
+        "DrkN" => DeepSkyObjectType::DarkNebula,
+        "EmN" => DeepSkyObjectType::EmissionNebula,
+        "Neb" => DeepSkyObjectType::Nebula,
+        "RfN" => DeepSkyObjectType::ReflectionNebula,
+        "SNR" => DeepSkyObjectType::SupernovaRemnant,
+        "Nova" => DeepSkyObjectType::Nova,
+        _ => DeepSkyObjectType::Other,
+    }
+}
+
+fn parse_optional(value: &Option<String>) -> anyhow::Result<Option<f64>> {
+    Ok(value
+        .as_deref()
+        .map(|value| value.trim().parse::<f64>())
+        .transpose()?)
+}
+
+fn parse_right_ascension(value: &str) -> anyhow::Result<f64> {
+    let mut parts = value.split(':');
+    let hours = parts.next().context("missing hours")?.parse::<u32>()?;
+    let minutes = parts.next().context("missing minutes")?.parse::<u32>()?;
+    let seconds = parts.next().context("missing seconds")?.parse::<f64>()?;
+    Ok(time_format_to_radians(' ', hours, minutes, seconds)?)
+}
+
+fn parse_declination(value: &str) -> anyhow::Result<f64> {
+    let sign = value.chars().next().context("missing sign")?;
+    let mut parts = value[sign.len_utf8()..].split(':');
+    let degrees = parts.next().context("missing degrees")?.parse::<u32>()?;
+    let minutes = parts.next().context("missing minutes")?.parse::<u32>()?;
+    let seconds = parts.next().context("missing seconds")?.parse::<f64>()?;
+    Ok(angle_format_to_radians(sign, degrees, minutes, seconds)?)
+}
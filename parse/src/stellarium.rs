@@ -0,0 +1,119 @@
+use anyhow::{bail, Context};
+use skyseeker_core::celestial_body::CelestialBody;
+use skyseeker_core::constellation::{Constellation, ConstellationFigure};
+use skyseeker_core::index::SkyIndex;
+use std::collections::{HashMap, HashSet};
+
+/// Farthest a Hipparcos star may be from its BSC5 counterpart, in degrees
+/// => Generous for the high proper motion stars, tight enough to keep close pairs apart
+const MAX_MATCH_SEPARATION: f64 = 0.05;
+
+/// Parses Stellarium's constellation stick figures (constellationship.fab)
+/// => One figure per line: abbreviation, number of lines, then the two stars of each line
+/// => Stars are given by Hipparcos number, matched to the loaded BSC5 stars by position
+pub fn parse_figures(
+    data: String,
+    hipparcos_data: String,
+    stars: &[CelestialBody],
+) -> anyhow::Result<Vec<ConstellationFigure>> {
+    let mut figures = Vec::new();
+    for line in data.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let abbreviation = fields.next().context("missing abbreviation")?;
+        let constellation = Constellation::from_abbreviation(abbreviation)
+            .with_context(|| format!("unknown constellation '{}'", abbreviation))?;
+        let count: usize = fields
+            .next()
+            .context("missing number of lines")?
+            .parse()
+            .with_context(|| format!("bad number of lines for '{}'", abbreviation))?;
+        let hipparcos_numbers = fields
+            .map(|field| field.parse::<u32>())
+            .collect::<Result<Vec<_>, _>>()
+            .with_context(|| format!("bad Hipparcos number for '{}'", abbreviation))?;
+        if hipparcos_numbers.len() != 2 * count {
+            bail!(
+                "expected {} stars for '{}', got {}",
+                2 * count,
+                abbreviation,
+                hipparcos_numbers.len()
+            );
+        }
+        figures.push((constellation, hipparcos_numbers));
+    }
+
+    let wanted: HashSet<u32> = figures
+        .iter()
+        .flat_map(|(_, hipparcos_numbers)| hipparcos_numbers.iter().copied())
+        .collect();
+    let places = parse_hipparcos_places(&hipparcos_data, &wanted)?;
+    let index = SkyIndex::from_bodies(stars);
+    let mut star_ids: HashMap<u32, String> = HashMap::new();
+    for (hipparcos_number, (right_ascension, declination)) in places {
+        match index
+            .nearest(right_ascension, declination, 1, f64::INFINITY)
+            .first()
+        {
+            Some(found) if found.separation <= MAX_MATCH_SEPARATION => {
+                star_ids.insert(hipparcos_number, found.body.id().to_string());
+            }
+            _ => println!(
+                "Skipping star 'HIP {}': no BSC5 star close by",
+                hipparcos_number
+            ),
+        }
+    }
+
+    Ok(figures
+        .into_iter()
+        .map(|(constellation, hipparcos_numbers)| ConstellationFigure {
+            abbreviation: constellation.abbreviation.to_string(),
+            lines: hipparcos_numbers
+                .chunks_exact(2)
+                .filter_map(|pair| {
+                    Some((
+                        star_ids.get(&pair[0])?.clone(),
+                        star_ids.get(&pair[1])?.clone(),
+                    ))
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+/// J2000.0 places of the wanted stars from the Hipparcos main catalog (hip_main.dat), angles in degrees
+/// => Fields are separated by '|', the Hipparcos number is the 2nd one, right ascension and declination the 9th and 10th
+fn parse_hipparcos_places(
+    data: &str,
+    wanted: &HashSet<u32>,
+) -> anyhow::Result<HashMap<u32, (f64, f64)>> {
+    let mut places = HashMap::new();
+    for line in data.lines() {
+        let fields: Vec<&str> = line.split('|').map(str::trim).collect();
+        let Some(Ok(hipparcos_number)) = fields.get(1).map(|field| field.parse::<u32>()) else {
+            continue;
+        };
+        if !wanted.contains(&hipparcos_number) {
+            continue;
+        }
+        let (Some(right_ascension), Some(declination)) = (fields.get(8), fields.get(9)) else {
+            bail!("missing place of 'HIP {}'", hipparcos_number);
+        };
+        // A few entries have no place of their own
+        let (Ok(right_ascension), Ok(declination)) =
+            (right_ascension.parse::<f64>(), declination.parse::<f64>())
+        else {
+            println!(
+                "Skipping star 'HIP {}': no place in Hipparcos",
+                hipparcos_number
+            );
+            continue;
+        };
+        places.insert(hipparcos_number, (right_ascension, declination));
+    }
+    Ok(places)
+}
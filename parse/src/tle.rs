@@ -0,0 +1,45 @@
+use anyhow::{bail, Context};
+use skyseeker_core::celestial_body::satellite::Satellite;
+use skyseeker_core::celestial_body::CelestialBody;
+use std::sync::Arc;
+
+/// Parses two-line element sets, with or without a title line before each set (2LE and 3LE)
+pub fn parse(data: String) -> anyhow::Result<Vec<CelestialBody>> {
+    let mut satellites = Vec::new();
+    let mut title: Option<&str> = None;
+    let mut lines = data
+        .lines()
+        .map(|line| line.trim_end())
+        .filter(|line| !line.is_empty());
+
+    while let Some(line) = lines.next() {
+        if !line.starts_with("1 ") {
+            // Celestrak's 3LE format prefixes the title with "0 "
+            title = Some(line.strip_prefix("0 ").unwrap_or(line).trim());
+            continue;
+        }
+
+        let name = title.take().map(|title| title.to_string());
+        let result = (|| -> anyhow::Result<CelestialBody> {
+            let line2 = lines.next().context("missing second line")?;
+            if !line2.starts_with("2 ") {
+                bail!("second line does not start with '2 '");
+            }
+
+            let satellite =
+                Satellite::from_tle(name.clone(), line, line2).context("failed to parse TLE")?;
+            Ok(CelestialBody::Satellite(Arc::new(satellite)))
+        })();
+
+        match result {
+            Ok(satellite) => satellites.push(satellite),
+            Err(e) => println!(
+                "Skipping entry '{}' in TLE: {}",
+                name.as_deref().unwrap_or(line),
+                e
+            ),
+        }
+    }
+
+    Ok(satellites)
+}